@@ -1,4 +1,5 @@
-use crate::Shape;
+use crate::{IndexCalculationError, InvalidStepError, OutOfShapeError, Shape};
+use std::fmt;
 use std::ops::{
     Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
 };
@@ -34,12 +35,197 @@ where
     }
 }
 
+pub trait IntoDummyIndexRange {
+    fn into_dummy_index_range(self) -> Box<dyn DummyIndexRange>;
+}
+
+impl IntoDummyIndexRange for RangeFull {
+    fn into_dummy_index_range(self) -> Box<dyn DummyIndexRange> {
+        Box::new(self)
+    }
+}
+
+// indexes beyond isize saturate, so that they are reported as out of shape instead of wrapping
+pub(crate) fn saturating_index<I>(value: I) -> isize
+where
+    I: Copy + Default + PartialOrd,
+    isize: TryFrom<I>,
+{
+    isize::try_from(value).unwrap_or(if value < I::default() {
+        isize::MIN
+    } else {
+        isize::MAX
+    })
+}
+
+macro_rules! into_dummy_index_range {
+    ($type:ty) => {
+        impl IntoDummyIndexRange for Range<$type> {
+            fn into_dummy_index_range(self) -> Box<dyn DummyIndexRange> {
+                Box::new(saturating_index(self.start)..saturating_index(self.end))
+            }
+        }
+
+        impl IntoDummyIndexRange for RangeFrom<$type> {
+            fn into_dummy_index_range(self) -> Box<dyn DummyIndexRange> {
+                Box::new(saturating_index(self.start)..)
+            }
+        }
+
+        impl IntoDummyIndexRange for RangeInclusive<$type> {
+            fn into_dummy_index_range(self) -> Box<dyn DummyIndexRange> {
+                Box::new(saturating_index(*self.start())..=saturating_index(*self.end()))
+            }
+        }
+
+        impl IntoDummyIndexRange for RangeTo<$type> {
+            fn into_dummy_index_range(self) -> Box<dyn DummyIndexRange> {
+                Box::new(..saturating_index(self.end))
+            }
+        }
+
+        impl IntoDummyIndexRange for RangeToInclusive<$type> {
+            fn into_dummy_index_range(self) -> Box<dyn DummyIndexRange> {
+                Box::new(..=saturating_index(self.end))
+            }
+        }
+
+        impl From<$type> for DummyIndex {
+            fn from(value: $type) -> Self {
+                Self::Index(saturating_index(value))
+            }
+        }
+
+        impl From<&[$type]> for DummyIndex {
+            fn from(indexes: &[$type]) -> Self {
+                Self::IndexArray(indexes.iter().copied().map(saturating_index).collect())
+            }
+        }
+
+        impl From<Vec<$type>> for DummyIndex {
+            fn from(indexes: Vec<$type>) -> Self {
+                Self::IndexArray(indexes.into_iter().map(saturating_index).collect())
+            }
+        }
+    };
+}
+
+into_dummy_index_range!(i8);
+into_dummy_index_range!(i16);
+into_dummy_index_range!(i32);
+into_dummy_index_range!(i64);
+into_dummy_index_range!(i128);
+into_dummy_index_range!(isize);
+into_dummy_index_range!(u8);
+into_dummy_index_range!(u16);
+into_dummy_index_range!(u32);
+into_dummy_index_range!(u64);
+into_dummy_index_range!(u128);
+into_dummy_index_range!(usize);
+
+pub(crate) fn actual_range<S: Shape>(
+    shape: &S,
+    dimension: usize,
+    range: &dyn DummyIndexRange,
+) -> Option<Range<usize>> {
+    let len = shape.len_of_dimension(dimension).unwrap();
+    let lower_bound = match range.start_bound() {
+        Bound::Included(value) => shape.actual_index(dimension, value),
+        Bound::Excluded(value) => shape.actual_index(dimension, value + 1),
+        Bound::Unbounded => Some(0),
+    };
+    let upper_bound = match range.end_bound() {
+        Bound::Included(value) => shape.actual_index(dimension, value).map(|index| index + 1),
+        Bound::Excluded(value)
+            if value == shape.lower_bound_of_dimension(dimension).unwrap() + len as isize =>
+        {
+            Some(len)
+        }
+        Bound::Excluded(value) => shape.actual_index(dimension, value),
+        Bound::Unbounded => Some(len),
+    };
+    match (lower_bound, upper_bound) {
+        (Some(start), Some(end)) if start <= end => Some(start..end),
+        _ => None,
+    }
+}
+
+pub(crate) fn out_of_shape<S: Shape>(
+    shape: &S,
+    dimension: usize,
+    index: isize,
+) -> IndexCalculationError {
+    IndexCalculationError::OutOfShape(OutOfShapeError {
+        dimension,
+        name: shape.names()[dimension].clone(),
+        lower_bound: shape.lower_bound_of_dimension(dimension).unwrap(),
+        len: shape.len_of_dimension(dimension).unwrap(),
+        vector_index: index,
+    })
+}
+
+pub(crate) fn checked_range<S: Shape>(
+    shape: &S,
+    dimension: usize,
+    range: &dyn DummyIndexRange,
+) -> Result<Range<usize>, IndexCalculationError> {
+    actual_range(shape, dimension, range).ok_or_else(|| {
+        out_of_shape(
+            shape,
+            dimension,
+            shape.len_of_dimension(dimension).unwrap() as isize,
+        )
+    })
+}
+
+pub(crate) fn actual_indexes<S: Shape>(
+    shape: &S,
+    dimension: usize,
+    indexes: &[isize],
+) -> Result<Vec<usize>, IndexCalculationError> {
+    let mut values = Vec::new();
+    for index in indexes.iter() {
+        match shape.actual_index(dimension, *index) {
+            Some(value) => values.push(value),
+            None => return Err(out_of_shape(shape, dimension, *index)),
+        }
+    }
+    Ok(values)
+}
+
+pub(crate) fn strided_indexes<S: Shape>(
+    shape: &S,
+    dimension: usize,
+    range: &dyn DummyIndexRange,
+    step: isize,
+) -> Result<Vec<usize>, IndexCalculationError> {
+    if step == 0 {
+        return Err(IndexCalculationError::InvalidStep(InvalidStepError {
+            dimension,
+            step,
+        }));
+    }
+    let range = checked_range(shape, dimension, range)?;
+    if step > 0 {
+        Ok(range.step_by(step as usize).collect())
+    } else {
+        Ok(range.rev().step_by((-step) as usize).collect())
+    }
+}
+
 enum DummyIndexIterator {
     Continuous(Range<usize>),
     Discrete(Vec<usize>),
 }
 
 impl DummyIndexIterator {
+    fn is_empty(&self) -> bool {
+        match self {
+            DummyIndexIterator::Continuous(range) => range.is_empty(),
+            DummyIndexIterator::Discrete(indexes) => indexes.is_empty(),
+        }
+    }
+
     fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = usize> + 'a> {
         match self {
             DummyIndexIterator::Continuous(range) => Box::new(range.clone().into_iter()),
@@ -51,106 +237,96 @@ impl DummyIndexIterator {
 pub enum DummyIndex {
     Index(isize),
     Range(Box<dyn DummyIndexRange>),
+    Strided {
+        range: Box<dyn DummyIndexRange>,
+        step: isize,
+    },
     IndexArray(Vec<isize>),
 }
 
 impl DummyIndex {
-    fn iterator_of<S: Shape>(&self, shape: &S, dimension: usize) -> DummyIndexIterator {
-        match self {
+    pub fn strided<R: IntoDummyIndexRange>(range: R, step: isize) -> Self {
+        Self::Strided {
+            range: range.into_dummy_index_range(),
+            step,
+        }
+    }
+
+    fn iterator_of<S: Shape>(
+        &self,
+        shape: &S,
+        dimension: usize,
+    ) -> Result<DummyIndexIterator, IndexCalculationError> {
+        Ok(match self {
             DummyIndex::Index(index) => match shape.actual_index(dimension, *index) {
                 Some(value) => DummyIndexIterator::Continuous(Range {
                     start: value,
                     end: value + 1,
                 }),
-                None => DummyIndexIterator::Continuous(Range { start: 0, end: 1 }),
+                None => return Err(out_of_shape(shape, dimension, *index)),
             },
             DummyIndex::Range(range) => {
-                let lower_bound = match range.start_bound() {
-                    Bound::Included(value) => shape.actual_index(dimension, value),
-                    Bound::Excluded(value) => shape.actual_index(dimension, value - 1),
-                    Bound::Unbounded => Some(0),
-                };
-                let upper_bound = match range.end_bound() {
-                    Bound::Included(value) => shape.actual_index(dimension, value + 1),
-                    Bound::Excluded(value) => shape.actual_index(dimension, value),
-                    Bound::Unbounded => Some(shape.len_of_dimension(dimension).unwrap()),
-                };
-                if lower_bound.is_some() && upper_bound.is_some() {
-                    DummyIndexIterator::Continuous(Range {
-                        start: lower_bound.unwrap(),
-                        end: upper_bound.unwrap(),
-                    })
-                } else {
-                    DummyIndexIterator::Continuous(Range { start: 0, end: 1 })
-                }
+                DummyIndexIterator::Continuous(checked_range(shape, dimension, range.as_ref())?)
             }
-            DummyIndex::IndexArray(indexes) => DummyIndexIterator::Discrete(
-                indexes
-                    .iter()
-                    .filter_map(move |index| shape.actual_index(dimension, *index))
-                    .collect(),
-            ),
-        }
-    }
-}
-
-impl From<isize> for DummyIndex {
-    fn from(value: isize) -> Self {
-        Self::Index(value)
-    }
-}
-
-impl From<usize> for DummyIndex {
-    fn from(value: usize) -> Self {
-        Self::Index(value as isize)
-    }
-}
-
-impl From<Range<isize>> for DummyIndex {
-    fn from(range: Range<isize>) -> Self {
-        Self::Range(Box::new(range))
-    }
-}
-
-impl From<RangeFrom<isize>> for DummyIndex {
-    fn from(range: RangeFrom<isize>) -> Self {
-        Self::Range(Box::new(range))
-    }
-}
-
-impl From<RangeInclusive<isize>> for DummyIndex {
-    fn from(range: RangeInclusive<isize>) -> Self {
-        Self::Range(Box::new(range))
-    }
-}
-
-impl From<RangeTo<isize>> for DummyIndex {
-    fn from(range: RangeTo<isize>) -> Self {
-        Self::Range(Box::new(range))
+            DummyIndex::Strided { range, step } => DummyIndexIterator::Discrete(strided_indexes(
+                shape,
+                dimension,
+                range.as_ref(),
+                *step,
+            )?),
+            DummyIndex::IndexArray(indexes) => {
+                DummyIndexIterator::Discrete(actual_indexes(shape, dimension, indexes)?)
+            }
+        })
     }
 }
 
-impl From<RangeToInclusive<isize>> for DummyIndex {
-    fn from(range: RangeToInclusive<isize>) -> Self {
-        Self::Range(Box::new(range))
+fn format_range(f: &mut fmt::Formatter<'_>, range: &dyn DummyIndexRange) -> fmt::Result {
+    match range.start_bound() {
+        Bound::Included(value) => write!(f, "{}", value)?,
+        Bound::Excluded(value) => write!(f, "{}", value + 1)?,
+        Bound::Unbounded => {}
+    };
+    match range.end_bound() {
+        Bound::Included(value) => write!(f, "..={}", value),
+        Bound::Excluded(value) => write!(f, "..{}", value),
+        Bound::Unbounded => write!(f, ".."),
     }
 }
 
-impl From<RangeFull> for DummyIndex {
-    fn from(range: RangeFull) -> Self {
-        Self::Range(Box::new(range))
+impl fmt::Display for DummyIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DummyIndex::Index(index) => {
+                write!(f, "{}", index)
+            }
+            DummyIndex::Range(range) => format_range(f, range.as_ref()),
+            DummyIndex::Strided { range, step } => {
+                write!(f, "(")?;
+                format_range(f, range.as_ref())?;
+                write!(f, ").step_by({})", step)
+            }
+            DummyIndex::IndexArray(indexes) => {
+                write!(f, "{:?}", indexes)
+            }
+        }
     }
 }
 
-impl From<&[isize]> for DummyIndex {
-    fn from(indexes: &[isize]) -> Self {
-        Self::IndexArray(indexes.to_vec())
+impl fmt::Debug for DummyIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DummyIndex::Index(_) => write!(f, "Index({})", self),
+            DummyIndex::Range(_) => write!(f, "Range({})", self),
+            DummyIndex::Strided { .. } => write!(f, "Strided({})", self),
+            DummyIndex::IndexArray(_) => write!(f, "IndexArray({})", self),
+        }
     }
 }
 
-impl From<Vec<isize>> for DummyIndex {
-    fn from(indexes: Vec<isize>) -> Self {
-        Self::IndexArray(indexes)
+impl<R: IntoDummyIndexRange> From<R> for DummyIndex {
+    fn from(range: R) -> Self {
+        Self::Range(range.into_dummy_index_range())
     }
 }
 
@@ -160,13 +336,16 @@ pub(crate) struct DummyAccessPolicy<'a, S: Shape> {
 }
 
 impl<'a, 'b, S: Shape> DummyAccessPolicy<'a, S> {
-    pub(crate) fn new(vector: &'a S::DummyVectorType, shape: &'a S) -> Self {
-        Self {
+    pub(crate) fn new(
+        vector: &'a S::DummyVectorType,
+        shape: &'a S,
+    ) -> Result<Self, IndexCalculationError> {
+        Ok(Self {
             shape: shape,
             iterators: (0..shape.dimension())
                 .map(|i| vector[i].iterator_of(shape, i))
-                .collect(),
-        }
+                .collect::<Result<Vec<_>, _>>()?,
+        })
     }
 
     pub(crate) fn iter(&'b self) -> DummyAccessIterator<'a, 'b, S> {
@@ -177,6 +356,8 @@ impl<'a, 'b, S: Shape> DummyAccessPolicy<'a, S> {
 pub(crate) struct DummyAccessIterator<'a, 'b, S: Shape> {
     pub(self) policy: *const DummyAccessPolicy<'a, S>,
     pub(self) iterators: Vec<Box<dyn Iterator<Item = usize> + 'b>>,
+    pub(self) lower_bounds: &'a [isize],
+    pub(self) empty: bool,
     pub(crate) now: S::VectorType,
 }
 
@@ -185,24 +366,32 @@ impl<'a, 'b, S: Shape> DummyAccessIterator<'a, 'b, S> {
         let mut ret = Self {
             policy: policy,
             iterators: policy.iterators.iter().map(|iter| iter.iter()).collect(),
+            lower_bounds: policy.shape.lower_bounds(),
+            empty: policy.iterators.iter().any(|iter| iter.is_empty()),
             now: policy.shape.zero(),
         };
+        if ret.empty {
+            return ret;
+        }
         for i in 0..(ret.iterators.len() - 1) {
-            ret.now[i] = ret.iterators[i].next().unwrap();
+            ret.now[i] = ret.iterators[i].next().unwrap() as isize + ret.lower_bounds[i];
         }
         ret
     }
 
     pub(crate) fn next(&mut self) -> Option<&S::VectorType> {
+        if self.empty {
+            return None;
+        }
         for i in (0..self.iterators.len()).rev() {
             match self.iterators[i].next() {
                 Some(value) => {
-                    self.now[i] = value;
+                    self.now[i] = value as isize + self.lower_bounds[i];
                     return Some(&self.now);
                 }
                 None => unsafe {
                     self.iterators[i] = (*self.policy).iterators[i].iter();
-                    self.now[i] = self.iterators[i].next().unwrap();
+                    self.now[i] = self.iterators[i].next().unwrap() as isize + self.lower_bounds[i];
                 },
             }
         }
@@ -210,25 +399,108 @@ impl<'a, 'b, S: Shape> DummyAccessIterator<'a, 'b, S> {
     }
 }
 
+#[doc(hidden)]
+#[macro_export]
 macro_rules! dummy_index {
+    (($($range:tt)*).step_by($($step:tt)*)) => {
+        $crate::dummy_vector::DummyIndex::strided(($($range)*), ($($step)*) as isize)
+    };
     ($x:literal) => {
-        DummyIndex::from($x as isize)
+        $crate::dummy_vector::DummyIndex::from($x as isize)
     };
     ($x:expr) => {
-        DummyIndex::from($x)
+        $crate::dummy_vector::DummyIndex::from($x)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! dummy_indexes {
+    ([$($indexes:expr,)*] []) => {
+        [$($indexes,)*]
+    };
+    ([$($indexes:expr,)*] [$($x:tt)+]) => {
+        [$($indexes,)* $crate::dummy_index!($($x)+),]
+    };
+    ([$($indexes:expr,)*] [$($x:tt)*] , $($rest:tt)*) => {
+        $crate::dummy_indexes!([$($indexes,)* $crate::dummy_index!($($x)*),] [] $($rest)*)
+    };
+    ([$($indexes:expr,)*] [$($x:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::dummy_indexes!([$($indexes,)*] [$($x)* $next] $($rest)*)
     };
 }
 
 #[macro_export]
 macro_rules! dummy {
-    ($($x:expr),*) => {
-        [$(dummy_index!($x),)*]
+    ($($x:tt)*) => {
+        $crate::dummy_indexes!([] [] $($x)*)
     };
 }
 
 #[macro_export]
 macro_rules! dyn_dummy {
-    ($($x:expr),*) => {
-        vec!($(dummy_index!($x),)*)
+    ($($x:tt)*) => {
+        Vec::<$crate::dummy_vector::DummyIndex>::from($crate::dummy_indexes!([] [] $($x)*))
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn zero_step() {
+        let mut array = MultiArray2::<i32>::new_by(Shape2::new([3, 4]), |i| i as i32);
+        assert!(matches!(
+            array.get(dummy!((0..3).step_by(0), ..)),
+            Err(IndexCalculationError::InvalidStep(_))
+        ));
+        assert!(matches!(
+            array.get_mut(dummy!((0..3).step_by(0), ..)),
+            Err(IndexCalculationError::InvalidStep(_))
+        ));
+    }
+
+    #[test]
+    fn empty_selection() {
+        let array = MultiArray2::<i32>::new_by(Shape2::new([3, 4]), |i| i as i32);
+        assert!(array.get(dummy!((0..0).step_by(1), ..)).unwrap().is_empty());
+        assert!(array.get(dummy!(.., 1..1)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn out_of_shape() {
+        let array = MultiArray2::<i32>::new_by(Shape2::new([3, 4]), |i| i as i32);
+        assert!(matches!(
+            array.get(dummy!(vec![5usize, 7], ..)),
+            Err(IndexCalculationError::OutOfShape(_))
+        ));
+        assert!(matches!(
+            array.get(dummy!(0..9, ..)),
+            Err(IndexCalculationError::OutOfShape(_))
+        ));
+    }
+
+    #[test]
+    fn oversized_indexes() {
+        let array = MultiArray2::<i32>::new_by(Shape2::new([3, 4]), |i| i as i32);
+        for vector in [
+            dummy!(usize::MAX, ..),
+            dummy!(u64::MAX, ..),
+            dummy!(i128::MIN, ..),
+            dummy!(vec![0, usize::MAX], ..),
+            dummy!(.., 0..u128::MAX),
+            dummy!(.., ..=u64::MAX),
+            dummy!(.., (0..usize::MAX).step_by(2)),
+        ] {
+            assert!(matches!(
+                array.get(vector),
+                Err(IndexCalculationError::OutOfShape(_))
+            ));
+        }
+        assert!(matches!(
+            array.map(map!(usize::MAX, map_vector::_0)),
+            Err(IndexCalculationError::OutOfShape(_))
+        ));
+    }
+}
@@ -0,0 +1,421 @@
+use crate::*;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+#[derive(Clone, Copy)]
+pub struct KeyNotFoundError {
+    pub dimension: usize,
+}
+
+impl fmt::Display for KeyNotFoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Key is not found in dimension {}.", self.dimension)
+    }
+}
+
+impl fmt::Debug for KeyNotFoundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Key is not found in dimension {}.", self.dimension)
+    }
+}
+
+#[derive(Clone)]
+pub enum KeySelectionError {
+    KeyNotFound(KeyNotFoundError),
+    IndexCalculation(IndexCalculationError),
+}
+
+impl fmt::Display for KeySelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeySelectionError::KeyNotFound(err) => write!(f, "{}", err),
+            KeySelectionError::IndexCalculation(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl fmt::Debug for KeySelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl From<KeyNotFoundError> for KeySelectionError {
+    fn from(err: KeyNotFoundError) -> Self {
+        KeySelectionError::KeyNotFound(err)
+    }
+}
+
+impl From<IndexCalculationError> for KeySelectionError {
+    fn from(err: IndexCalculationError) -> Self {
+        KeySelectionError::IndexCalculation(err)
+    }
+}
+
+#[derive(Clone)]
+pub struct KeyedAxis<K: Eq + Hash + Clone> {
+    pub(self) keys: Vec<K>,
+    pub(self) positions: HashMap<K, usize>,
+}
+
+impl<K: Eq + Hash + Clone> KeyedAxis<K> {
+    pub fn new<I: IntoIterator<Item = K>>(keys: I) -> Self {
+        let mut ret = Self {
+            keys: Vec::new(),
+            positions: HashMap::new(),
+        };
+        for key in keys {
+            if !ret.positions.contains_key(&key) {
+                ret.positions.insert(key.clone(), ret.keys.len());
+                ret.keys.push(key);
+            }
+        }
+        ret
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    pub fn position_of(&self, key: &K) -> Option<usize> {
+        self.positions.get(key).copied()
+    }
+
+    pub fn key_of(&self, position: usize) -> Option<&K> {
+        self.keys.get(position)
+    }
+
+    pub(self) fn dummy_of(
+        &self,
+        dimension: usize,
+        selection: KeySelection<'_, K>,
+    ) -> Result<DummyIndex, KeyNotFoundError> {
+        let position_of = |key: &K| match self.position_of(key) {
+            Some(position) => Ok(position as isize),
+            None => Err(KeyNotFoundError { dimension }),
+        };
+        Ok(match selection {
+            KeySelection::All => DummyIndex::from(..),
+            KeySelection::Key(key) => DummyIndex::Index(position_of(key)?),
+            KeySelection::Keys(keys) => DummyIndex::IndexArray(
+                keys.iter()
+                    .map(position_of)
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        })
+    }
+}
+
+impl<K: Eq + Hash + Clone> FromIterator<K> for KeyedAxis<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(keys: I) -> Self {
+        Self::new(keys)
+    }
+}
+
+pub enum KeySelection<'k, K> {
+    All,
+    Key(&'k K),
+    Keys(&'k [K]),
+}
+
+pub trait KeyedAxes {
+    type Shape: Shape;
+    type Key<'k>
+    where
+        Self: 'k;
+    type Selection<'k>
+    where
+        Self: 'k;
+
+    fn shape(&self) -> Self::Shape;
+    fn vector_of<'k>(
+        &self,
+        key: Self::Key<'k>,
+    ) -> Result<<Self::Shape as Shape>::VectorType, KeyNotFoundError>;
+    fn key_of(&self, vector: &<Self::Shape as Shape>::VectorType) -> Self::Key<'_>;
+    fn dummy_of<'k>(
+        &self,
+        selection: Self::Selection<'k>,
+    ) -> Result<Vec<DummyIndex>, KeyNotFoundError>;
+}
+
+pub struct KeyedMultiArray<A: KeyedAxes, T: Sized> {
+    pub(self) axes: A,
+    pub(self) array: MultiArray<T, A::Shape>,
+}
+
+impl<A: KeyedAxes, T: Sized> KeyedMultiArray<A, T> {
+    pub fn new(axes: A) -> Self {
+        let array = MultiArray::new(axes.shape());
+        Self { axes, array }
+    }
+
+    pub fn new_with(axes: A, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let array = MultiArray::new_with(axes.shape(), value);
+        Self { axes, array }
+    }
+
+    pub fn new_by<G>(axes: A, generator: G) -> Self
+    where
+        G: Fn(A::Key<'_>) -> T,
+    {
+        let shape = axes.shape();
+        let array = MultiArray::new_by(shape.clone(), |index| {
+            generator(axes.key_of(&shape.vector(index)))
+        });
+        Self { axes, array }
+    }
+
+    pub fn from_multi_array(
+        axes: A,
+        mut array: MultiArray<T, A::Shape>,
+    ) -> Result<Self, DimensionMismatchingError> {
+        let shape = axes.shape();
+        for i in 0..shape.dimension() {
+            if shape.shape()[i] != array.shape.shape()[i] {
+                return Err(DimensionMismatchingError {
+                    dimension: shape.shape()[i],
+                    vector_dimension: array.shape.shape()[i],
+                });
+            }
+        }
+        array.shape = with_lower_bounds(array.shape, &vec![0; shape.dimension()]);
+        Ok(Self { axes, array })
+    }
+
+    pub fn axes(&self) -> &A {
+        &self.axes
+    }
+
+    pub fn array(&self) -> &MultiArray<T, A::Shape> {
+        &self.array
+    }
+
+    pub fn array_mut(&mut self) -> &mut MultiArray<T, A::Shape> {
+        &mut self.array
+    }
+
+    pub fn into_array(self) -> MultiArray<T, A::Shape> {
+        self.array
+    }
+
+    pub fn get_by_key(&self, key: A::Key<'_>) -> Result<Option<&T>, KeyNotFoundError> {
+        let vector = self.axes.vector_of(key)?;
+        Ok(self.array.try_get(&vector).unwrap())
+    }
+
+    pub fn get_by_key_mut(&mut self, key: A::Key<'_>) -> Result<Option<&mut T>, KeyNotFoundError> {
+        let vector = self.axes.vector_of(key)?;
+        Ok(self.array.try_get_mut(&vector).unwrap())
+    }
+
+    pub fn set_by_key(&mut self, key: A::Key<'_>, value: T) -> Result<Option<T>, KeyNotFoundError> {
+        let vector = self.axes.vector_of(key)?;
+        Ok(self.array.replace(&vector, value).unwrap())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (A::Key<'_>, &T)> {
+        self.array
+            .indexed_iter()
+            .map(move |(vector, value)| (self.axes.key_of(&vector), value))
+    }
+
+    // selecting a single key on every axis leaves no dimension for the view, which is an error
+    pub fn select(
+        &self,
+        selection: A::Selection<'_>,
+    ) -> Result<MultiArrayView<'_, T, DynShape>, KeySelectionError> {
+        let selection = self.axes.dummy_of(selection)?;
+        Ok(self
+            .array
+            .select(selection.into_iter().enumerate().collect())?)
+    }
+
+    pub fn select_mut(
+        &mut self,
+        selection: A::Selection<'_>,
+    ) -> Result<MultiArrayViewMut<'_, T, DynShape>, KeySelectionError> {
+        let selection = self.axes.dummy_of(selection)?;
+        Ok(self
+            .array
+            .select_mut(selection.into_iter().enumerate().collect())?)
+    }
+}
+
+macro_rules! keyed_axes {
+    ($shape:ident, $alias:ident, $($key:ident: $index:tt),+) => {
+        impl<$($key: Eq + Hash + Clone),+> KeyedAxes for ($(KeyedAxis<$key>,)+) {
+            type Shape = $shape;
+            type Key<'k> = ($(&'k $key,)+) where Self: 'k;
+            type Selection<'k> = ($(KeySelection<'k, $key>,)+) where Self: 'k;
+
+            fn shape(&self) -> Self::Shape {
+                $shape::new([$(self.$index.len()),+])
+            }
+
+            fn vector_of<'k>(
+                &self,
+                key: Self::Key<'k>,
+            ) -> Result<<Self::Shape as Shape>::VectorType, KeyNotFoundError> {
+                Ok([$(match self.$index.position_of(key.$index) {
+                    Some(position) => position as isize,
+                    None => return Err(KeyNotFoundError { dimension: $index }),
+                }),+])
+            }
+
+            fn key_of(&self, vector: &<Self::Shape as Shape>::VectorType) -> Self::Key<'_> {
+                ($(&self.$index.keys[vector[$index] as usize],)+)
+            }
+
+            fn dummy_of<'k>(
+                &self,
+                selection: Self::Selection<'k>,
+            ) -> Result<Vec<DummyIndex>, KeyNotFoundError> {
+                Ok(vec![$(self.$index.dummy_of($index, selection.$index)?),+])
+            }
+        }
+
+        pub type $alias<$($key),+, T> = KeyedMultiArray<($(KeyedAxis<$key>,)+), T>;
+    };
+}
+
+keyed_axes!(Shape1, KeyedMultiArray1, K0: 0);
+keyed_axes!(Shape2, KeyedMultiArray2, K0: 0, K1: 1);
+keyed_axes!(Shape3, KeyedMultiArray3, K0: 0, K1: 1, K2: 2);
+keyed_axes!(Shape4, KeyedMultiArray4, K0: 0, K1: 1, K2: 2, K3: 3);
+keyed_axes!(Shape5, KeyedMultiArray5, K0: 0, K1: 1, K2: 2, K3: 3, K4: 4);
+keyed_axes!(Shape6, KeyedMultiArray6, K0: 0, K1: 1, K2: 2, K3: 3, K4: 4, K5: 5);
+keyed_axes!(Shape7, KeyedMultiArray7, K0: 0, K1: 1, K2: 2, K3: 3, K4: 4, K5: 5, K6: 6);
+keyed_axes!(Shape8, KeyedMultiArray8, K0: 0, K1: 1, K2: 2, K3: 3, K4: 4, K5: 5, K6: 6, K7: 7);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn array() -> KeyedMultiArray2<&'static str, u32, i32> {
+        let axes = (
+            KeyedAxis::new(["a", "b", "a", "c"]),
+            KeyedAxis::from_iter([10, 20]),
+        );
+        KeyedMultiArray::new_by(axes, |(row, column)| {
+            (row.as_bytes()[0] - b'a') as i32 * 100 + *column as i32
+        })
+    }
+
+    #[test]
+    fn keys() {
+        let axis = KeyedAxis::new(["a", "b", "a", "c"]);
+        assert_eq!(axis.len(), 3);
+        assert_eq!(axis.keys(), &["a", "b", "c"]);
+        assert_eq!(axis.position_of(&"c"), Some(2));
+        assert_eq!(axis.position_of(&"d"), None);
+        assert_eq!(axis.key_of(1), Some(&"b"));
+        assert!(KeyedAxis::<u32>::new([]).is_empty());
+    }
+
+    #[test]
+    fn from_multi_array() {
+        let axes = (KeyedAxis::new(["x", "y"]), KeyedAxis::new([1, 2, 3]));
+        let shape = Shape2::new([2, 3]).with_lower_bounds([1, 1]);
+        let inner = MultiArray2::new_by(shape, |i| i as i32);
+        let array = KeyedMultiArray2::from_multi_array(axes.clone(), inner).unwrap();
+        assert_eq!(array.array().shape().lower_bounds(), &[0, 0]);
+        assert_eq!(array.get_by_key((&"y", &1)).unwrap(), Some(&3));
+
+        let inner = MultiArray2::new_by(Shape2::new([3, 2]), |i| i as i32);
+        assert!(KeyedMultiArray2::from_multi_array(axes, inner).is_err());
+    }
+
+    #[test]
+    fn get_and_set_by_key() {
+        let mut array = array();
+        assert_eq!(array.get_by_key((&"c", &20)).unwrap(), Some(&220));
+        assert_eq!(array.set_by_key((&"b", &10), 0).unwrap(), Some(110));
+        *array.get_by_key_mut((&"a", &20)).unwrap().unwrap() += 1;
+        assert_eq!(array.array()[&[0, 1]], 21);
+        assert_eq!(array.array()[&[1, 0]], 0);
+        assert_eq!(array.get_by_key((&"d", &10)).err().unwrap().dimension, 0);
+        assert_eq!(array.set_by_key((&"a", &30), 1).err().unwrap().dimension, 1);
+    }
+
+    #[test]
+    fn iter() {
+        let array = array();
+        let values: Vec<_> = array
+            .iter()
+            .map(|((row, column), value)| (*row, *column, *value))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                ("a", 10, 10),
+                ("a", 20, 20),
+                ("b", 10, 110),
+                ("b", 20, 120),
+                ("c", 10, 210),
+                ("c", 20, 220)
+            ]
+        );
+    }
+
+    #[test]
+    fn select() {
+        let mut array = array();
+        let rows = ["c", "a"];
+        let view = array
+            .select((KeySelection::Keys(&rows), KeySelection::All))
+            .unwrap();
+        assert_eq!(view.shape().shape(), &[2, 2]);
+        assert_eq!(
+            view.iter().cloned().collect::<Vec<_>>(),
+            vec![210, 220, 10, 20]
+        );
+
+        let view = array
+            .select((KeySelection::All, KeySelection::Key(&20)))
+            .unwrap();
+        assert_eq!(view.iter().cloned().collect::<Vec<_>>(), vec![20, 120, 220]);
+
+        assert!(matches!(
+            array.select((KeySelection::Key(&"a"), KeySelection::Key(&10))),
+            Err(KeySelectionError::IndexCalculation(_))
+        ));
+        assert!(matches!(
+            array.select((KeySelection::Key(&"d"), KeySelection::All)),
+            Err(KeySelectionError::KeyNotFound(KeyNotFoundError {
+                dimension: 0
+            }))
+        ));
+
+        let columns = [20];
+        array
+            .select_mut((KeySelection::Key(&"b"), KeySelection::Keys(&columns)))
+            .unwrap()
+            .fill(0);
+        assert_eq!(array.get_by_key((&"b", &20)).unwrap(), Some(&0));
+        assert_eq!(array.get_by_key((&"b", &10)).unwrap(), Some(&110));
+        assert!(matches!(
+            array.select_mut((KeySelection::Key(&"c"), KeySelection::Key(&10))),
+            Err(KeySelectionError::IndexCalculation(_))
+        ));
+        let missing = [30];
+        assert!(matches!(
+            array.select_mut((KeySelection::All, KeySelection::Keys(&missing))),
+            Err(KeySelectionError::KeyNotFound(KeyNotFoundError {
+                dimension: 1
+            }))
+        ));
+    }
+}
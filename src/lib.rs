@@ -3,13 +3,34 @@
 #![feature(generators, generator_trait)]
 #[macro_use]
 pub mod dummy_vector;
+pub mod keyed_multi_array;
 #[macro_use]
 pub mod map_vector;
 pub mod multi_array;
+pub mod multi_array_entry;
+mod multi_array_format;
+pub mod multi_array_iterator;
+pub mod multi_array_operation;
+#[cfg(feature = "rayon")]
+mod multi_array_parallel;
+pub mod multi_array_record;
+pub mod multi_array_reduction;
 pub mod multi_array_view;
+pub mod npy;
+#[cfg(feature = "serde")]
+mod serialization;
 pub mod shape;
+pub mod storage;
 
-pub use dummy_vector::DummyIndex;
+pub use dummy_vector::{DummyIndex, IntoDummyIndexRange};
+pub use keyed_multi_array::*;
+pub use map_vector::{MapIndex, PlaceHolder};
 pub use multi_array::*;
+pub use multi_array_entry::*;
+pub use multi_array_iterator::*;
+pub use multi_array_record::*;
+pub use multi_array_reduction::*;
 pub use multi_array_view::*;
+pub use npy::*;
 pub use shape::*;
+pub use storage::*;
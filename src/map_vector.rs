@@ -1,124 +1,358 @@
-use crate::dummy_vector::DummyIndexRange;
-use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+use crate::dummy_vector::{
+    actual_indexes, actual_range, out_of_shape, saturating_index, strided_indexes, DummyIndex,
+    DummyIndexRange, IntoDummyIndexRange,
+};
+use crate::multi_array_view::ViewAxis;
+use crate::shape::{with_lower_bounds, with_names};
+use crate::{DimensionMismatchingError, DynShape, IndexCalculationError, Shape};
+use std::ops::Range;
 
+#[derive(Clone, Copy)]
 pub struct PlaceHolder {
-    index: usize,
+    pub(crate) index: usize,
 }
 
-const _0: PlaceHolder = PlaceHolder { index: 0 };
-const _1: PlaceHolder = PlaceHolder { index: 1 };
-const _2: PlaceHolder = PlaceHolder { index: 2 };
-const _3: PlaceHolder = PlaceHolder { index: 3 };
-const _4: PlaceHolder = PlaceHolder { index: 4 };
-const _5: PlaceHolder = PlaceHolder { index: 5 };
-const _6: PlaceHolder = PlaceHolder { index: 6 };
-const _7: PlaceHolder = PlaceHolder { index: 7 };
-const _8: PlaceHolder = PlaceHolder { index: 8 };
-const _9: PlaceHolder = PlaceHolder { index: 9 };
-const _10: PlaceHolder = PlaceHolder { index: 10 };
-const _11: PlaceHolder = PlaceHolder { index: 11 };
-const _12: PlaceHolder = PlaceHolder { index: 12 };
-const _13: PlaceHolder = PlaceHolder { index: 13 };
-const _14: PlaceHolder = PlaceHolder { index: 14 };
-const _15: PlaceHolder = PlaceHolder { index: 15 };
-const _16: PlaceHolder = PlaceHolder { index: 16 };
-const _17: PlaceHolder = PlaceHolder { index: 17 };
-const _18: PlaceHolder = PlaceHolder { index: 18 };
-const _19: PlaceHolder = PlaceHolder { index: 19 };
-const _20: PlaceHolder = PlaceHolder { index: 20 };
-
-pub(super) enum MapIndex<'a> {
+pub const _0: PlaceHolder = PlaceHolder { index: 0 };
+pub const _1: PlaceHolder = PlaceHolder { index: 1 };
+pub const _2: PlaceHolder = PlaceHolder { index: 2 };
+pub const _3: PlaceHolder = PlaceHolder { index: 3 };
+pub const _4: PlaceHolder = PlaceHolder { index: 4 };
+pub const _5: PlaceHolder = PlaceHolder { index: 5 };
+pub const _6: PlaceHolder = PlaceHolder { index: 6 };
+pub const _7: PlaceHolder = PlaceHolder { index: 7 };
+pub const _8: PlaceHolder = PlaceHolder { index: 8 };
+pub const _9: PlaceHolder = PlaceHolder { index: 9 };
+pub const _10: PlaceHolder = PlaceHolder { index: 10 };
+pub const _11: PlaceHolder = PlaceHolder { index: 11 };
+pub const _12: PlaceHolder = PlaceHolder { index: 12 };
+pub const _13: PlaceHolder = PlaceHolder { index: 13 };
+pub const _14: PlaceHolder = PlaceHolder { index: 14 };
+pub const _15: PlaceHolder = PlaceHolder { index: 15 };
+pub const _16: PlaceHolder = PlaceHolder { index: 16 };
+pub const _17: PlaceHolder = PlaceHolder { index: 17 };
+pub const _18: PlaceHolder = PlaceHolder { index: 18 };
+pub const _19: PlaceHolder = PlaceHolder { index: 19 };
+pub const _20: PlaceHolder = PlaceHolder { index: 20 };
+
+pub enum MapIndex<'a> {
     Index(isize),
     Range(Box<dyn DummyIndexRange>),
-    IndexArray(Box<dyn Iterator<Item = isize> + 'a>),
+    IndexArray(&'a [isize]),
     Map(PlaceHolder),
 }
 
-impl<'a> From<isize> for MapIndex<'a> {
-    fn from(value: isize) -> Self {
-        Self::Index(value)
-    }
+macro_rules! map_index_from {
+    ($type:ty) => {
+        impl<'a> From<$type> for MapIndex<'a> {
+            fn from(value: $type) -> Self {
+                Self::Index(saturating_index(value))
+            }
+        }
+    };
 }
 
-impl<'a> From<usize> for MapIndex<'a> {
-    fn from(value: usize) -> Self {
-        Self::Index(value as isize)
+map_index_from!(i8);
+map_index_from!(i16);
+map_index_from!(i32);
+map_index_from!(i64);
+map_index_from!(i128);
+map_index_from!(isize);
+map_index_from!(u8);
+map_index_from!(u16);
+map_index_from!(u32);
+map_index_from!(u64);
+map_index_from!(u128);
+map_index_from!(usize);
+
+impl<'a, R: IntoDummyIndexRange> From<R> for MapIndex<'a> {
+    fn from(range: R) -> Self {
+        Self::Range(range.into_dummy_index_range())
     }
 }
 
-impl<'a> From<Range<isize>> for MapIndex<'a> {
-    fn from(range: Range<isize>) -> Self {
-        Self::Range(Box::new(range))
+impl<'a> From<&'a [isize]> for MapIndex<'a> {
+    fn from(indexes: &'a [isize]) -> Self {
+        Self::IndexArray(indexes)
     }
 }
 
-impl<'a> From<RangeFrom<isize>> for MapIndex<'a> {
-    fn from(range: RangeFrom<isize>) -> Self {
-        Self::Range(Box::new(range))
+impl<'a> From<PlaceHolder> for MapIndex<'a> {
+    fn from(holder: PlaceHolder) -> Self {
+        Self::Map(holder)
     }
 }
 
-impl<'a> From<RangeInclusive<isize>> for MapIndex<'a> {
-    fn from(range: RangeInclusive<isize>) -> Self {
-        Self::Range(Box::new(range))
-    }
+pub(crate) enum MapAxis {
+    Fixed(usize),
+    Continuous(Range<usize>),
+    Discrete(Vec<usize>),
 }
 
-impl<'a> From<RangeTo<isize>> for MapIndex<'a> {
-    fn from(range: RangeTo<isize>) -> Self {
-        Self::Range(Box::new(range))
-    }
+pub(crate) struct MapAccessPolicy {
+    pub(crate) axes: Vec<MapAxis>,
+    pub(crate) dimensions: Vec<usize>,
+    pub(crate) shape: DynShape,
 }
 
-impl<'a> From<RangeToInclusive<isize>> for MapIndex<'a> {
-    fn from(range: RangeToInclusive<isize>) -> Self {
-        Self::Range(Box::new(range))
+impl MapAccessPolicy {
+    pub(crate) fn new<S: Shape>(
+        vector: &S::MapVectorType<'_>,
+        shape: &S,
+    ) -> Result<Self, IndexCalculationError> {
+        let mut axes = Vec::new();
+        let mut holders = Vec::new();
+        let mut frees = Vec::new();
+        for i in 0..shape.dimension() {
+            axes.push(match &vector[i] {
+                MapIndex::Index(index) => match shape.actual_index(i, *index) {
+                    Some(value) => MapAxis::Fixed(value),
+                    None => return Err(out_of_shape(shape, i, *index)),
+                },
+                MapIndex::Range(range) => match actual_range(shape, i, range.as_ref()) {
+                    Some(range) => {
+                        frees.push(i);
+                        MapAxis::Continuous(range)
+                    }
+                    None => {
+                        return Err(out_of_shape(
+                            shape,
+                            i,
+                            shape.len_of_dimension(i).unwrap() as isize,
+                        ))
+                    }
+                },
+                MapIndex::IndexArray(indexes) => {
+                    frees.push(i);
+                    MapAxis::Discrete(actual_indexes(shape, i, indexes)?)
+                }
+                MapIndex::Map(holder) => {
+                    holders.push((holder.index, i));
+                    MapAxis::Continuous(0..shape.len_of_dimension(i).unwrap())
+                }
+            });
+        }
+        Ok(Self::with_axes(axes, holders, frees)?.named(shape))
     }
-}
 
-impl<'a> From<RangeFull> for MapIndex<'a> {
-    fn from(range: RangeFull) -> Self {
-        Self::Range(Box::new(range))
+    // repeated indexes in an index array are kept once, so that no cell is selected twice
+    pub(crate) fn from_dummy<S: Shape>(
+        vector: &S::DummyVectorType,
+        shape: &S,
+    ) -> Result<Self, IndexCalculationError> {
+        let mut axes = Vec::new();
+        let mut frees = Vec::new();
+        for i in 0..shape.dimension() {
+            axes.push(match &vector[i] {
+                DummyIndex::Index(index) => match shape.actual_index(i, *index) {
+                    Some(value) => MapAxis::Fixed(value),
+                    None => return Err(out_of_shape(shape, i, *index)),
+                },
+                DummyIndex::Range(range) => match actual_range(shape, i, range.as_ref()) {
+                    Some(range) => {
+                        frees.push(i);
+                        MapAxis::Continuous(range)
+                    }
+                    None => {
+                        return Err(out_of_shape(
+                            shape,
+                            i,
+                            shape.len_of_dimension(i).unwrap() as isize,
+                        ))
+                    }
+                },
+                DummyIndex::Strided { range, step } => {
+                    let indexes = strided_indexes(shape, i, range.as_ref(), *step)?;
+                    frees.push(i);
+                    MapAxis::Discrete(indexes)
+                }
+                DummyIndex::IndexArray(indexes) => {
+                    let mut values = Vec::new();
+                    for value in actual_indexes(shape, i, indexes)? {
+                        if !values.contains(&value) {
+                            values.push(value);
+                        }
+                    }
+                    frees.push(i);
+                    MapAxis::Discrete(values)
+                }
+            });
+        }
+        Ok(Self::with_axes(axes, Vec::new(), frees)?.named(shape))
     }
-}
 
-impl<'a> From<&'a [isize]> for MapIndex<'a> {
-    fn from(indexes: &'a [isize]) -> Self {
-        Self::IndexArray(Box::new(indexes.iter().map(|index| *index)))
+    pub(self) fn named<S: Shape>(mut self, shape: &S) -> Self {
+        let names: Vec<_> = self
+            .dimensions
+            .iter()
+            .map(|dimension| shape.names()[*dimension].clone())
+            .collect();
+        let lower_bounds: Vec<_> = self
+            .dimensions
+            .iter()
+            .map(|dimension| match &self.axes[*dimension] {
+                MapAxis::Continuous(range) => {
+                    shape.lower_bounds()[*dimension] + range.start as isize
+                }
+                _ => 0,
+            })
+            .collect();
+        self.shape = with_lower_bounds(with_names(self.shape, &names), &lower_bounds);
+        self
     }
-}
 
-impl<'a> From<PlaceHolder> for MapIndex<'a> {
-    fn from(holder: PlaceHolder) -> Self {
-        Self::Map(holder)
+    pub(self) fn with_axes(
+        axes: Vec<MapAxis>,
+        holders: Vec<(usize, usize)>,
+        frees: Vec<usize>,
+    ) -> Result<Self, IndexCalculationError> {
+        let dimension = holders.len() + frees.len();
+        if dimension == 0 {
+            return Err(IndexCalculationError::DimensionMismatching(
+                DimensionMismatchingError {
+                    dimension: 1,
+                    vector_dimension: 0,
+                },
+            ));
+        }
+        let mut dimensions: Vec<Option<usize>> = (0..dimension).map(|_| None).collect();
+        for (index, i) in holders {
+            if index >= dimension || dimensions[index].is_some() {
+                return Err(IndexCalculationError::DimensionMismatching(
+                    DimensionMismatchingError {
+                        dimension,
+                        vector_dimension: index,
+                    },
+                ));
+            }
+            dimensions[index] = Some(i);
+        }
+        let mut frees = frees.into_iter();
+        let dimensions: Vec<usize> = dimensions
+            .into_iter()
+            .map(|i| i.unwrap_or_else(|| frees.next().unwrap()))
+            .collect();
+        let shape = DynShape::new(
+            dimensions
+                .iter()
+                .map(|i| match &axes[*i] {
+                    MapAxis::Fixed(_) => 1,
+                    MapAxis::Continuous(range) => range.len(),
+                    MapAxis::Discrete(indexes) => indexes.len(),
+                })
+                .collect(),
+        );
+        Ok(Self {
+            axes,
+            dimensions,
+            shape,
+        })
     }
-}
 
-// pub(super) struct MapAccessPolicy<'a, S: Shape> {
-//     vector: &'a S::MapVectorType,
-//     shape: S,
-//     now: RefCell<S::VectorType>,
-// }
+    pub(crate) fn layout<S: Shape>(&self, shape: &S) -> (usize, Vec<ViewAxis>) {
+        let mut base = 0;
+        for (i, axis) in self.axes.iter().enumerate() {
+            let offset = shape.offset_of_dimension(i).unwrap();
+            match axis {
+                MapAxis::Fixed(value) => base += value * offset,
+                MapAxis::Continuous(range) => base += range.start * offset,
+                MapAxis::Discrete(_) => {}
+            }
+        }
+        let axes = self
+            .dimensions
+            .iter()
+            .map(|i| {
+                let mut axis = ViewAxis::new(shape.offset_of_dimension(*i).unwrap() as isize);
+                if let MapAxis::Discrete(indexes) = &self.axes[*i] {
+                    axis.indexes = Some(indexes.clone());
+                }
+                axis
+            })
+            .collect();
+        (base, axes)
+    }
+}
 
+#[doc(hidden)]
+#[macro_export]
 macro_rules! map_index {
     ($x:literal) => {
-        MapIndex::from($x as isize)
+        $crate::map_vector::MapIndex::from($x as isize)
     };
     ($x:expr) => {
-        MapIndex::from($x)
+        $crate::map_vector::MapIndex::from($x)
     };
 }
 
 #[macro_export]
 macro_rules! map {
     ($($x:expr),*) => {
-        &[$(map_index!($x),)*]
+        &[$($crate::map_index!($x),)*]
     };
 }
 
 #[macro_export]
 macro_rules! dyn_map {
     ($($x:expr),*) => {
-        &vec!($(map_index!($x),)*)
+        &vec!($($crate::map_index!($x),)*)
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::map_vector::*;
+    use crate::*;
+
+    fn values(view: MultiArrayView<'_, i32, DynShape>) -> Vec<i32> {
+        view.iter().cloned().collect()
+    }
+
+    #[test]
+    fn reorder_axes() {
+        let array = MultiArray3::<i32>::new_by(Shape3::new([2, 3, 4]), |i| i as i32);
+        let view = array.map(map!(_1, _0, 2)).unwrap();
+        assert_eq!(view.shape().shape(), &[3, 2]);
+        assert_eq!(values(view), vec![2, 14, 6, 18, 10, 22]);
+
+        let array = MultiArray::<i32, DynShape>::new_by(DynShape::new(vec![2, 3, 4]), |i| i as i32);
+        let view = array.map(dyn_map!(_2, _0, _1)).unwrap();
+        assert_eq!(view.shape().shape(), &[3, 4, 2]);
+        assert_eq!(view[&vec![2, 1, 0]], 9);
+        assert_eq!(view[&vec![0, 3, 1]], 15);
+    }
+
+    #[test]
+    fn duplicate_place_holder() {
+        let array = MultiArray3::<i32>::new_by(Shape3::new([2, 3, 4]), |i| i as i32);
+        assert!(matches!(
+            array.map(map!(_0, _0, 1)),
+            Err(IndexCalculationError::DimensionMismatching(_))
+        ));
+    }
+
+    #[test]
+    fn place_holder_out_of_dimension() {
+        let array = MultiArray3::<i32>::new_by(Shape3::new([2, 3, 4]), |i| i as i32);
+        assert!(matches!(
+            array.map(map!(_2, 0, 1)),
+            Err(IndexCalculationError::DimensionMismatching(_))
+        ));
+        assert!(matches!(
+            array.map(map!(_0, _3, ..)),
+            Err(IndexCalculationError::DimensionMismatching(_))
+        ));
+    }
+
+    #[test]
+    fn mixed_indexes() {
+        let array = MultiArray3::<i32>::new_by(Shape3::new([2, 3, 4]), |i| i as i32);
+        let indexes = [3isize, 0];
+        let view = array.map(map!(_1, 1..3, &indexes[..])).unwrap();
+        assert_eq!(view.shape().shape(), &[2, 2, 2]);
+        assert_eq!(values(view), vec![7, 4, 19, 16, 11, 8, 23, 20]);
+        assert!(matches!(
+            array.map(map!(_0, 1..3, &[4isize][..])),
+            Err(IndexCalculationError::OutOfShape(_))
+        ));
+    }
+}
@@ -1,10 +1,237 @@
 use crate::dummy_vector::*;
+use crate::map_vector::*;
+use crate::multi_array_view::ViewAxis;
 use crate::*;
+use std::borrow::Cow;
+use std::iter::{Product, Sum};
+use std::marker::PhantomData;
 use std::ops::{Index, IndexMut};
 
-pub struct MultiArray<T: Sized, S: Shape> {
-    pub(self) list: Vec<Option<T>>,
-    pub(self) shape: S,
+pub struct MultiArray<T: Sized, S: Shape, St: Storage<T> = Vec<Option<T>>> {
+    pub(crate) list: St,
+    pub(crate) shape: S,
+    pub(crate) _marker: PhantomData<T>,
+}
+
+impl<T: Sized, S: Shape, St: Storage<T>> MultiArray<T, S, St> {
+    pub fn from_storage(shape: S, list: St) -> Result<Self, LengthMismatchingError> {
+        if list.len_of_cells() != shape.len() {
+            return Err(LengthMismatchingError {
+                len: list.len_of_cells(),
+                shape_len: shape.len(),
+            });
+        }
+        Ok(Self {
+            list,
+            shape,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn shape(&self) -> &S {
+        &self.shape
+    }
+
+    pub fn len(&self) -> usize {
+        self.shape.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn reshape<S2: Shape>(self, shape: S2) -> Result<MultiArray<T, S2, St>, ReshapeError> {
+        if shape.len() != self.shape.len() {
+            return Err(ReshapeError::LengthMismatching(LengthMismatchingError {
+                len: self.shape.len(),
+                shape_len: shape.len(),
+            }));
+        }
+        let mut list = self.list;
+        if self.shape.layout() != Layout::RowMajor || shape.layout() != Layout::RowMajor {
+            let (from, to) = (row_major(&self.shape), row_major(&shape));
+            let mut sources = vec![0; shape.len()];
+            for i in 0..shape.len() {
+                sources[shape.index(&to.vector(i)).unwrap()] =
+                    self.shape.index(&from.vector(i)).unwrap();
+            }
+            let mut visited = vec![false; shape.len()];
+            for start in 0..shape.len() {
+                let mut i = start;
+                while !visited[i] && sources[i] != start {
+                    visited[i] = true;
+                    list.swap_cells(i, sources[i]);
+                    i = sources[i];
+                }
+                visited[i] = true;
+            }
+        }
+        Ok(MultiArray {
+            list,
+            shape,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn into_storage<St2: PartialStorage<T>>(self) -> MultiArray<T, S, St2> {
+        let mut list = St2::empty(self.shape.len());
+        for (index, value) in self.list.into_cells() {
+            list.set_cell(index, value);
+        }
+        MultiArray {
+            list,
+            shape: self.shape,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(self) fn checked_index(&self, index: usize) -> Result<usize, IndexCalculationError> {
+        if index >= self.shape.len() {
+            Err(IndexCalculationError::OutOfShape(OutOfShapeError {
+                dimension: 0,
+                name: None,
+                lower_bound: 0,
+                len: self.shape.len(),
+                vector_index: index as isize,
+            }))
+        } else {
+            Ok(index)
+        }
+    }
+
+    pub fn try_get_at(&self, index: usize) -> Result<Option<&T>, IndexCalculationError> {
+        Ok(self.list.cell(self.checked_index(index)?))
+    }
+
+    pub fn try_get(&self, vector: &S::VectorType) -> Result<Option<&T>, IndexCalculationError> {
+        Ok(self.list.cell(self.shape.index(vector)?))
+    }
+
+    pub fn try_get_mut_at(
+        &mut self,
+        index: usize,
+    ) -> Result<Option<&mut T>, IndexCalculationError> {
+        let index = self.checked_index(index)?;
+        Ok(self.list.cell_mut(index))
+    }
+
+    pub fn try_get_mut(
+        &mut self,
+        vector: &S::VectorType,
+    ) -> Result<Option<&mut T>, IndexCalculationError> {
+        let index = self.shape.index(vector)?;
+        Ok(self.list.cell_mut(index))
+    }
+
+    pub fn is_initialized_at(&self, index: usize) -> bool {
+        index < self.shape.len() && self.list.cell(index).is_some()
+    }
+
+    pub fn is_initialized(&self, vector: &S::VectorType) -> bool {
+        match self.shape.index(vector) {
+            Ok(index) => self.list.cell(index).is_some(),
+            Err(_) => false,
+        }
+    }
+
+    pub fn set_at(&mut self, index: usize, value: T) -> Result<(), IndexCalculationError> {
+        self.replace_at(index, value).map(|_| ())
+    }
+
+    pub fn set(&mut self, vector: &S::VectorType, value: T) -> Result<(), IndexCalculationError> {
+        self.replace(vector, value).map(|_| ())
+    }
+
+    pub fn replace_at(
+        &mut self,
+        index: usize,
+        value: T,
+    ) -> Result<Option<T>, IndexCalculationError> {
+        let index = self.checked_index(index)?;
+        Ok(self.list.set_cell(index, value))
+    }
+
+    pub fn replace(
+        &mut self,
+        vector: &S::VectorType,
+        value: T,
+    ) -> Result<Option<T>, IndexCalculationError> {
+        let index = self.shape.index(vector)?;
+        Ok(self.list.set_cell(index, value))
+    }
+
+    pub fn take_at(&mut self, index: usize) -> Result<Option<T>, IndexCalculationError>
+    where
+        St: PartialStorage<T>,
+    {
+        let index = self.checked_index(index)?;
+        Ok(self.list.take_cell(index))
+    }
+
+    pub fn take(&mut self, vector: &S::VectorType) -> Result<Option<T>, IndexCalculationError>
+    where
+        St: PartialStorage<T>,
+    {
+        let index = self.shape.index(vector)?;
+        Ok(self.list.take_cell(index))
+    }
+
+    pub fn get_or_insert_with_at<F>(
+        &mut self,
+        index: usize,
+        f: F,
+    ) -> Result<&mut T, IndexCalculationError>
+    where
+        F: FnOnce() -> T,
+    {
+        let index = self.checked_index(index)?;
+        if self.list.cell(index).is_none() {
+            self.list.set_cell(index, f());
+        }
+        Ok(self.list.cell_mut(index).unwrap())
+    }
+
+    pub fn get_or_insert_with<F>(
+        &mut self,
+        vector: &S::VectorType,
+        f: F,
+    ) -> Result<&mut T, IndexCalculationError>
+    where
+        F: FnOnce() -> T,
+    {
+        let index = self.shape.index(vector)?;
+        self.get_or_insert_with_at(index, f)
+    }
+
+    pub fn iter(&self) -> MultiArrayIterator<'_, T, St> {
+        MultiArrayIterator::new(&self.list)
+    }
+
+    pub fn iter_mut(&mut self) -> MultiArrayIteratorMut<'_, T, St> {
+        MultiArrayIteratorMut::new(&mut self.list)
+    }
+
+    pub fn indexed_iter(&self) -> MultiArrayIndexedIterator<'_, T, S, St> {
+        MultiArrayIndexedIterator::new(&self.list, &self.shape)
+    }
+
+    pub fn to_records(&self) -> MultiArrayIndexedIterator<'_, T, S, St> {
+        self.indexed_iter()
+    }
+
+    pub fn get(
+        &self,
+        vector: S::DummyVectorType,
+    ) -> Result<Vec<Option<&T>>, IndexCalculationError> {
+        let mut ret = Vec::new();
+        let policy = DummyAccessPolicy::new(&vector, &self.shape)?;
+        let mut iter = policy.iter();
+        while let Some(vector) = iter.next() {
+            let index = self.shape.index(vector).unwrap();
+            ret.push(self.list.cell(index));
+        }
+        Ok(ret)
+    }
 }
 
 impl<T: Sized, S: Shape> MultiArray<T, S> {
@@ -12,6 +239,7 @@ impl<T: Sized, S: Shape> MultiArray<T, S> {
         Self {
             list: (0..shape.len()).map(|_| Option::None).collect(),
             shape: shape,
+            _marker: PhantomData,
         }
     }
 
@@ -24,6 +252,7 @@ impl<T: Sized, S: Shape> MultiArray<T, S> {
                 .map(|_| Option::Some(value.clone()))
                 .collect(),
             shape: shape,
+            _marker: PhantomData,
         }
     }
 
@@ -36,35 +265,640 @@ impl<T: Sized, S: Shape> MultiArray<T, S> {
                 .map(|index| Option::Some(generator(index)))
                 .collect(),
             shape: shape,
+            _marker: PhantomData,
         }
     }
 
-    pub fn get(&self, vector: S::DummyVectorType) -> Result<Vec<&Option<T>>, OutOfShapeError> {
-        let mut ret = Vec::new();
-        let policy = DummyAccessPolicy::new(&vector, &self.shape);
-        let mut iter = policy.iter();
-        loop {
-            match iter.next() {
-                Some(vector) => {
-                    let index = self.shape.index(vector).unwrap();
-                    ret.push(&self.list[index]);
+    pub fn from_records<I>(shape: S, records: I) -> Result<Self, RecordError>
+    where
+        I: IntoIterator<Item = (S::VectorType, T)>,
+    {
+        let mut ret = Self::new(shape);
+        for (line, (vector, value)) in records.into_iter().enumerate() {
+            let index = ret
+                .shape
+                .index(&vector)
+                .map_err(|err| RecordError::from_index_error(line + 1, err))?;
+            ret.list[index] = Some(value);
+        }
+        Ok(ret)
+    }
+
+    pub fn to_layout(&self, layout: Layout) -> Result<Self, DimensionMismatchingError>
+    where
+        T: Clone,
+    {
+        let shape = self.shape.clone().with_layout(layout)?;
+        let axes = self
+            .shape
+            .offset()
+            .iter()
+            .map(|offset| ViewAxis::new(*offset as isize))
+            .collect();
+        Ok(MultiArrayView::new(&self.list, shape, 0, axes).to_multi_array())
+    }
+
+    pub(self) fn row_major_list(&self) -> Cow<'_, [Option<T>]>
+    where
+        T: Clone,
+    {
+        match self.shape.layout() {
+            Layout::RowMajor => Cow::Borrowed(&self.list),
+            _ => Cow::Owned(self.to_layout(Layout::RowMajor).unwrap().list),
+        }
+    }
+
+    pub fn concatenate(
+        dimension: usize,
+        arrays: &[&Self],
+    ) -> Result<Self, DimensionMismatchingError>
+    where
+        T: Clone,
+    {
+        let first = match arrays.first() {
+            Some(first) => first,
+            None => {
+                return Err(DimensionMismatchingError {
+                    dimension: 1,
+                    vector_dimension: 0,
+                });
+            }
+        };
+        if dimension >= first.shape.dimension() {
+            return Err(DimensionMismatchingError {
+                dimension: first.shape.dimension(),
+                vector_dimension: dimension,
+            });
+        }
+        let mut shape = first.shape.shape().to_vec();
+        shape[dimension] = 0;
+        for array in arrays {
+            if array.shape.dimension() != first.shape.dimension() {
+                return Err(DimensionMismatchingError {
+                    dimension: first.shape.dimension(),
+                    vector_dimension: array.shape.dimension(),
+                });
+            }
+            for (i, len) in shape.iter().enumerate() {
+                if i != dimension && array.shape.shape()[i] != *len {
+                    return Err(DimensionMismatchingError {
+                        dimension: *len,
+                        vector_dimension: array.shape.shape()[i],
+                    });
                 }
-                None => {
-                    break;
+            }
+            shape[dimension] += array.shape.shape()[dimension];
+        }
+        let lists: Vec<_> = arrays.iter().map(|array| array.row_major_list()).collect();
+        let outer: usize = shape[..dimension].iter().product();
+        let mut list = Vec::with_capacity(shape.iter().product());
+        for i in 0..outer {
+            for (array, array_list) in arrays.iter().zip(lists.iter()) {
+                let block = array.shape.shape()[dimension..].iter().product::<usize>();
+                list.extend_from_slice(&array_list[(i * block)..((i + 1) * block)]);
+            }
+        }
+        Ok(Self {
+            list,
+            shape: with_lower_bounds(
+                with_names(S::from_slice(&shape)?, first.shape.names()),
+                first.shape.lower_bounds(),
+            ),
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn stack(
+        arrays: &[&Self],
+    ) -> Result<MultiArray<T, S::ExpandedShape>, DimensionMismatchingError>
+    where
+        T: Clone,
+        S: ExpandableShape,
+    {
+        let first = match arrays.first() {
+            Some(first) => first,
+            None => {
+                return Err(DimensionMismatchingError {
+                    dimension: 1,
+                    vector_dimension: 0,
+                });
+            }
+        };
+        let mut list = Vec::with_capacity(arrays.len() * first.shape.len());
+        for array in arrays {
+            if array.shape.dimension() != first.shape.dimension() {
+                return Err(DimensionMismatchingError {
+                    dimension: first.shape.dimension(),
+                    vector_dimension: array.shape.dimension(),
+                });
+            }
+            for i in 0..first.shape.dimension() {
+                if array.shape.shape()[i] != first.shape.shape()[i] {
+                    return Err(DimensionMismatchingError {
+                        dimension: first.shape.shape()[i],
+                        vector_dimension: array.shape.shape()[i],
+                    });
                 }
             }
+            list.extend_from_slice(&array.row_major_list());
         }
-        Ok(ret)
+        Ok(MultiArray {
+            list,
+            shape: first.shape.expand(0, arrays.len())?,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn view(&self) -> MultiArrayView<'_, T, S> {
+        let axes = self
+            .shape
+            .offset()
+            .iter()
+            .map(|offset| ViewAxis::new(*offset as isize))
+            .collect();
+        MultiArrayView::new(&self.list, self.shape.clone(), 0, axes)
+    }
+
+    pub fn view_mut(&mut self) -> MultiArrayViewMut<'_, T, S> {
+        let axes = self
+            .shape
+            .offset()
+            .iter()
+            .map(|offset| ViewAxis::new(*offset as isize))
+            .collect();
+        MultiArrayViewMut::new(&mut self.list, self.shape.clone(), 0, axes)
+    }
+
+    pub fn split_at(
+        &self,
+        dimension: usize,
+        index: usize,
+    ) -> Result<SplitView<'_, T, S>, IndexCalculationError> {
+        self.view().split_at(dimension, index)
+    }
+
+    pub fn split_at_mut(
+        &mut self,
+        dimension: usize,
+        index: usize,
+    ) -> Result<SplitViewMut<'_, T, S>, IndexCalculationError> {
+        self.view_mut().split_at_mut(dimension, index)
+    }
+
+    pub fn chunks(
+        &self,
+        dimension: usize,
+        size: usize,
+    ) -> Result<Vec<MultiArrayView<'_, T, S>>, IndexCalculationError> {
+        self.view().chunks(dimension, size)
+    }
+
+    pub fn chunks_mut(
+        &mut self,
+        dimension: usize,
+        size: usize,
+    ) -> Result<Vec<MultiArrayViewMut<'_, T, S>>, IndexCalculationError> {
+        self.view_mut().chunks_mut(dimension, size)
+    }
+
+    pub fn reshape_view<S2: Shape>(
+        &self,
+        shape: S2,
+    ) -> Result<MultiArrayView<'_, T, S2>, ReshapeError> {
+        if shape.len() != self.shape.len() {
+            return Err(ReshapeError::LengthMismatching(LengthMismatchingError {
+                len: self.shape.len(),
+                shape_len: shape.len(),
+            }));
+        }
+        if self.shape.layout() != Layout::RowMajor {
+            return Err(ReshapeError::LayoutMismatching(LayoutMismatchingError {
+                layout: self.shape.layout(),
+            }));
+        }
+        let axes = row_major(&shape)
+            .offset()
+            .iter()
+            .map(|offset| ViewAxis::new(*offset as isize))
+            .collect();
+        Ok(MultiArrayView::new(&self.list, shape, 0, axes))
+    }
+
+    pub fn permute_axes<A: Axis>(
+        &self,
+        axes: &[A],
+    ) -> Result<MultiArrayView<'_, T, S>, IndexCalculationError> {
+        let dimension = self.shape.dimension();
+        if axes.len() != dimension {
+            return Err(IndexCalculationError::DimensionMismatching(
+                DimensionMismatchingError {
+                    dimension,
+                    vector_dimension: axes.len(),
+                },
+            ));
+        }
+        let mut dimensions = Vec::with_capacity(dimension);
+        for axis in axes {
+            let axis = axis
+                .dimension_of(&self.shape)
+                .map_err(IndexCalculationError::UnknownDimension)?;
+            if axis >= dimension || dimensions.contains(&axis) {
+                return Err(IndexCalculationError::DimensionMismatching(
+                    DimensionMismatchingError {
+                        dimension,
+                        vector_dimension: axis,
+                    },
+                ));
+            }
+            dimensions.push(axis);
+        }
+        let shape = S::from_slice(
+            &dimensions
+                .iter()
+                .map(|axis| self.shape.shape()[*axis])
+                .collect::<Vec<_>>(),
+        )
+        .map_err(IndexCalculationError::DimensionMismatching)?;
+        let names: Vec<_> = dimensions
+            .iter()
+            .map(|axis| self.shape.names()[*axis].clone())
+            .collect();
+        let lower_bounds: Vec<_> = dimensions
+            .iter()
+            .map(|axis| self.shape.lower_bounds()[*axis])
+            .collect();
+        let axes = dimensions
+            .iter()
+            .map(|axis| ViewAxis::new(self.shape.offset()[*axis] as isize))
+            .collect();
+        Ok(MultiArrayView::new(
+            &self.list,
+            with_lower_bounds(with_names(shape, &names), &lower_bounds),
+            0,
+            axes,
+        ))
+    }
+
+    pub fn permuted_axes<A: Axis>(&self, axes: &[A]) -> Result<Self, IndexCalculationError>
+    where
+        T: Clone,
+    {
+        Ok(self.permute_axes(axes)?.to_multi_array())
+    }
+
+    pub fn broadcast_to<S2: Shape>(
+        &self,
+        shape: &S2,
+    ) -> Result<MultiArrayView<'_, T, S2>, IndexCalculationError> {
+        if shape.dimension() < self.shape.dimension() {
+            return Err(IndexCalculationError::DimensionMismatching(
+                DimensionMismatchingError {
+                    dimension: self.shape.dimension(),
+                    vector_dimension: shape.dimension(),
+                },
+            ));
+        }
+        let skip = shape.dimension() - self.shape.dimension();
+        let mut axes = Vec::with_capacity(shape.dimension());
+        for i in 0..shape.dimension() {
+            if i < skip || self.shape.shape()[i - skip] == 1 {
+                axes.push(ViewAxis::new(0));
+            } else if self.shape.shape()[i - skip] == shape.shape()[i] {
+                axes.push(ViewAxis::new(self.shape.offset()[i - skip] as isize));
+            } else {
+                return Err(IndexCalculationError::ShapeMismatching(
+                    ShapeMismatchingError {
+                        dimension: i,
+                        len: self.shape.shape()[i - skip],
+                        broadcast_len: shape.shape()[i],
+                    },
+                ));
+            }
+        }
+        Ok(MultiArrayView::new(&self.list, shape.clone(), 0, axes))
+    }
+
+    pub fn strict(&self) -> MultiArrayReduction<'_, T, S> {
+        MultiArrayReduction::new(self, true)
+    }
+
+    pub fn fold_axis<'a, A: Axis, U, F>(
+        &'a self,
+        axis: A,
+        init: U,
+        fold: F,
+    ) -> Result<MultiArray<U, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        U: Clone,
+        F: Fn(U, &'a T) -> U,
+    {
+        MultiArrayReduction::new(self, false).fold_axis(axis, init, fold)
+    }
+
+    pub fn sum_axis<'a, A: Axis>(
+        &'a self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        T: Sum<&'a T>,
+    {
+        MultiArrayReduction::new(self, false).sum_axis(axis)
+    }
+
+    pub fn product_axis<'a, A: Axis>(
+        &'a self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        T: Product<&'a T>,
+    {
+        MultiArrayReduction::new(self, false).product_axis(axis)
     }
 
-    // fn map<'a>(&'a self, vector: &S::MapVectorType) -> MultiArrayView<'a, T, DynShape> {}
+    pub fn min_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        T: PartialOrd + Clone,
+    {
+        MultiArrayReduction::new(self, false).min_axis(axis)
+    }
+
+    pub fn max_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        T: PartialOrd + Clone,
+    {
+        MultiArrayReduction::new(self, false).max_axis(axis)
+    }
+
+    pub fn fold<'a, U, F>(&'a self, init: U, fold: F) -> U
+    where
+        F: Fn(U, &'a T) -> U,
+    {
+        self.iter().fold(init, fold)
+    }
+
+    pub fn sum<'a>(&'a self) -> T
+    where
+        T: Sum<&'a T>,
+    {
+        self.iter().sum()
+    }
+
+    pub fn product<'a>(&'a self) -> T
+    where
+        T: Product<&'a T>,
+    {
+        self.iter().product()
+    }
+
+    pub fn min(&self) -> Option<&T>
+    where
+        T: PartialOrd,
+    {
+        MultiArrayReduction::new(self, false).min().unwrap()
+    }
+
+    pub fn max(&self) -> Option<&T>
+    where
+        T: PartialOrd,
+    {
+        MultiArrayReduction::new(self, false).max().unwrap()
+    }
+
+    pub fn get_mut(
+        &mut self,
+        vector: S::DummyVectorType,
+    ) -> Result<MultiArrayViewMut<'_, T, DynShape>, IndexCalculationError> {
+        let policy = MapAccessPolicy::from_dummy(&vector, &self.shape)?;
+        let (base, axes) = policy.layout(&self.shape);
+        Ok(MultiArrayViewMut::new(
+            &mut self.list,
+            policy.shape,
+            base,
+            axes,
+        ))
+    }
+
+    pub(self) fn selection_of<A: Axis>(
+        &self,
+        selection: Vec<(A, DummyIndex)>,
+    ) -> Result<(DynShape, MapAccessPolicy), IndexCalculationError> {
+        let shape = with_lower_bounds(
+            with_names(
+                DynShape::from_slice(self.shape.shape())
+                    .and_then(|shape| shape.with_layout(self.shape.layout()))
+                    .map_err(IndexCalculationError::DimensionMismatching)?,
+                self.shape.names(),
+            ),
+            self.shape.lower_bounds(),
+        );
+        let mut vector: Vec<DummyIndex> = (0..shape.dimension())
+            .map(|_| DummyIndex::from(..))
+            .collect();
+        for (axis, index) in selection {
+            let dimension = axis
+                .dimension_of(&shape)
+                .map_err(IndexCalculationError::UnknownDimension)?;
+            if dimension >= vector.len() {
+                return Err(IndexCalculationError::DimensionMismatching(
+                    DimensionMismatchingError {
+                        dimension: vector.len(),
+                        vector_dimension: dimension,
+                    },
+                ));
+            }
+            vector[dimension] = index;
+        }
+        let policy = MapAccessPolicy::from_dummy(&vector, &shape)?;
+        Ok((shape, policy))
+    }
+
+    pub fn select<A: Axis>(
+        &self,
+        selection: Vec<(A, DummyIndex)>,
+    ) -> Result<MultiArrayView<'_, T, DynShape>, IndexCalculationError> {
+        let (shape, policy) = self.selection_of(selection)?;
+        let (base, axes) = policy.layout(&shape);
+        Ok(MultiArrayView::new(&self.list, policy.shape, base, axes))
+    }
+
+    pub fn select_mut<A: Axis>(
+        &mut self,
+        selection: Vec<(A, DummyIndex)>,
+    ) -> Result<MultiArrayViewMut<'_, T, DynShape>, IndexCalculationError> {
+        let (shape, policy) = self.selection_of(selection)?;
+        let (base, axes) = policy.layout(&shape);
+        Ok(MultiArrayViewMut::new(
+            &mut self.list,
+            policy.shape,
+            base,
+            axes,
+        ))
+    }
+
+    pub fn map<'a>(
+        &'a self,
+        vector: &S::MapVectorType<'_>,
+    ) -> Result<MultiArrayView<'a, T, DynShape>, IndexCalculationError> {
+        let policy = MapAccessPolicy::new(vector, &self.shape)?;
+        let (base, axes) = policy.layout(&self.shape);
+        Ok(MultiArrayView::new(&self.list, policy.shape, base, axes))
+    }
+}
+
+impl<T: Sized, S: Shape> MultiArray<T, S, SparseStorage<T>> {
+    pub fn new_sparse(shape: S) -> Self {
+        Self {
+            list: SparseStorage::new(shape.len()),
+            shape,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Sized, S: Shape> MultiArray<T, S, Vec<T>> {
+    pub fn new_dense_with(shape: S, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            list: (0..shape.len()).map(|_| value.clone()).collect(),
+            shape,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn new_dense_by<G>(shape: S, generator: G) -> Self
+    where
+        G: Fn(usize) -> T,
+    {
+        Self {
+            list: (0..shape.len()).map(generator).collect(),
+            shape,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.list
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.list
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.list
+    }
+
+    pub fn into_partial(self) -> MultiArray<T, S> {
+        MultiArray {
+            list: self.list.into_iter().map(Option::Some).collect(),
+            shape: self.shape,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Sized, S: Shape> MultiArray<T, S> {
+    pub fn entry_at(&mut self, index: usize) -> Result<Entry<'_, T>, IndexCalculationError> {
+        let index = self.checked_index(index)?;
+        Ok(Entry::new(&mut self.list[index], index))
+    }
+
+    pub fn entry(&mut self, vector: &S::VectorType) -> Result<Entry<'_, T>, IndexCalculationError> {
+        let index = self.shape.index(vector)?;
+        Ok(Entry::new(&mut self.list[index], index))
+    }
+
+    pub fn into_dense(self) -> Result<DenseMultiArray<T, S>, UninitializedError> {
+        if let Some(index) = self.list.iter().position(|cell| cell.is_none()) {
+            return Err(UninitializedError { index });
+        }
+        Ok(MultiArray {
+            list: self.list.into_iter().flatten().collect(),
+            shape: self.shape,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: Sized, S: Shape> From<DenseMultiArray<T, S>> for MultiArray<T, S> {
+    fn from(array: DenseMultiArray<T, S>) -> Self {
+        array.into_partial()
+    }
+}
+
+impl<T: Sized, S: Shape> TryFrom<MultiArray<T, S>> for DenseMultiArray<T, S> {
+    type Error = UninitializedError;
+
+    fn try_from(array: MultiArray<T, S>) -> Result<Self, Self::Error> {
+        array.into_dense()
+    }
+}
+
+impl<T: Sized> MultiArray<T, Shape2> {
+    pub fn transpose(&self) -> MultiArrayView<'_, T, Shape2> {
+        self.permute_axes(&[1, 0]).unwrap()
+    }
+
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transpose().to_multi_array()
+    }
 }
 
-impl<T: Sized, S: Shape> Index<usize> for MultiArray<T, S> {
+impl<T: Sized, S: Shape, St: Storage<T>> IntoIterator for MultiArray<T, S, St> {
+    type Item = T;
+    type IntoIter = MultiArrayIntoIterator<T, St>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        MultiArrayIntoIterator::new(self.list)
+    }
+}
+
+impl<'a, T: Sized, S: Shape, St: Storage<T>> IntoIterator for &'a MultiArray<T, S, St> {
+    type Item = &'a T;
+    type IntoIter = MultiArrayIterator<'a, T, St>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Sized, S: Shape, St: Storage<T>> IntoIterator for &'a mut MultiArray<T, S, St> {
+    type Item = &'a mut T;
+    type IntoIter = MultiArrayIteratorMut<'a, T, St>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T: Sized, S: Shape, St: Storage<T>> Index<usize> for MultiArray<T, S, St> {
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
-        match &self.list[index] {
+        if index >= self.list.len_of_cells() {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.list.len_of_cells(),
+                index
+            );
+        }
+        match self.list.cell(index) {
             Some(value) => value,
             None => {
                 panic!(
@@ -76,9 +910,16 @@ impl<T: Sized, S: Shape> Index<usize> for MultiArray<T, S> {
     }
 }
 
-impl<T: Sized, S: Shape> IndexMut<usize> for MultiArray<T, S> {
+impl<T: Sized, S: Shape, St: Storage<T>> IndexMut<usize> for MultiArray<T, S, St> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        match &mut self.list[index] {
+        if index >= self.list.len_of_cells() {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.list.len_of_cells(),
+                index
+            );
+        }
+        match self.list.cell_mut(index) {
             Some(value) => value,
             None => {
                 panic!(
@@ -90,12 +931,12 @@ impl<T: Sized, S: Shape> IndexMut<usize> for MultiArray<T, S> {
     }
 }
 
-impl<T: Sized, S: Shape> Index<&S::VectorType> for MultiArray<T, S> {
+impl<T: Sized, S: Shape, St: Storage<T>> Index<&S::VectorType> for MultiArray<T, S, St> {
     type Output = T;
 
     fn index(&self, vector: &S::VectorType) -> &Self::Output {
         match self.shape.index(vector) {
-            Ok(index) => match &self.list[index] {
+            Ok(index) => match self.list.cell(index) {
                 Some(value) => value,
                 None => {
                     panic!(
@@ -109,10 +950,10 @@ impl<T: Sized, S: Shape> Index<&S::VectorType> for MultiArray<T, S> {
     }
 }
 
-impl<T: Sized, S: Shape> IndexMut<&S::VectorType> for MultiArray<T, S> {
+impl<T: Sized, S: Shape, St: Storage<T>> IndexMut<&S::VectorType> for MultiArray<T, S, St> {
     fn index_mut(&mut self, vector: &S::VectorType) -> &mut Self::Output {
         match self.shape.index(vector) {
-            Ok(index) => match &mut self.list[index] {
+            Ok(index) => match self.list.cell_mut(index) {
                 Some(value) => value,
                 None => {
                     panic!(
@@ -126,34 +967,36 @@ impl<T: Sized, S: Shape> IndexMut<&S::VectorType> for MultiArray<T, S> {
     }
 }
 
-type MultiArray1<T> = MultiArray<T, Shape1>;
-type MultiArray2<T> = MultiArray<T, Shape2>;
-type MultiArray3<T> = MultiArray<T, Shape3>;
-type MultiArray4<T> = MultiArray<T, Shape4>;
-type MultiArray5<T> = MultiArray<T, Shape5>;
-type MultiArray6<T> = MultiArray<T, Shape6>;
-type MultiArray7<T> = MultiArray<T, Shape7>;
-type MultiArray8<T> = MultiArray<T, Shape8>;
-type MultiArray9<T> = MultiArray<T, Shape9>;
-type MultiArray10<T> = MultiArray<T, Shape10>;
-type MultiArray11<T> = MultiArray<T, Shape11>;
-type MultiArray12<T> = MultiArray<T, Shape12>;
-type MultiArray13<T> = MultiArray<T, Shape13>;
-type MultiArray14<T> = MultiArray<T, Shape14>;
-type MultiArray15<T> = MultiArray<T, Shape15>;
-type MultiArray16<T> = MultiArray<T, Shape16>;
-type MultiArray17<T> = MultiArray<T, Shape17>;
-type MultiArray18<T> = MultiArray<T, Shape18>;
-type MultiArray19<T> = MultiArray<T, Shape19>;
-type MultiArray20<T> = MultiArray<T, Shape20>;
-type DynMultiArray<T> = MultiArray<T, DynShape>;
+pub type MultiArray1<T> = MultiArray<T, Shape1>;
+pub type MultiArray2<T> = MultiArray<T, Shape2>;
+pub type MultiArray3<T> = MultiArray<T, Shape3>;
+pub type MultiArray4<T> = MultiArray<T, Shape4>;
+pub type MultiArray5<T> = MultiArray<T, Shape5>;
+pub type MultiArray6<T> = MultiArray<T, Shape6>;
+pub type MultiArray7<T> = MultiArray<T, Shape7>;
+pub type MultiArray8<T> = MultiArray<T, Shape8>;
+pub type MultiArray9<T> = MultiArray<T, Shape9>;
+pub type MultiArray10<T> = MultiArray<T, Shape10>;
+pub type MultiArray11<T> = MultiArray<T, Shape11>;
+pub type MultiArray12<T> = MultiArray<T, Shape12>;
+pub type MultiArray13<T> = MultiArray<T, Shape13>;
+pub type MultiArray14<T> = MultiArray<T, Shape14>;
+pub type MultiArray15<T> = MultiArray<T, Shape15>;
+pub type MultiArray16<T> = MultiArray<T, Shape16>;
+pub type MultiArray17<T> = MultiArray<T, Shape17>;
+pub type MultiArray18<T> = MultiArray<T, Shape18>;
+pub type MultiArray19<T> = MultiArray<T, Shape19>;
+pub type MultiArray20<T> = MultiArray<T, Shape20>;
+pub type DynMultiArray<T> = MultiArray<T, DynShape>;
+pub type SparseMultiArray<T, S> = MultiArray<T, S, SparseStorage<T>>;
+pub type DenseMultiArray<T, S> = MultiArray<T, S, Vec<T>>;
 
 macro_rules! vector_index {
     ($x:literal) => {
-        $x as usize
+        $x as isize
     };
     ($x:expr) => {
-        $x as usize
+        $x as isize
     };
 }
 
@@ -170,3 +1013,331 @@ macro_rules! dyn_vector {
         vec!($(vector_index!($x),)*)
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::shape::row_major;
+    use crate::*;
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn sparse_index_out_of_bounds() {
+        let array = SparseMultiArray::<i32, Shape1>::new_sparse(Shape1::new([2]));
+        let _ = array[2];
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn sparse_index_mut_out_of_bounds() {
+        let mut array = SparseMultiArray::<i32, Shape1>::new_sparse(Shape1::new([2]));
+        array[5] = 1;
+    }
+
+    fn row_major_values<S: Shape>(array: &MultiArray<i32, S>) -> Vec<i32> {
+        let order = row_major(array.shape());
+        (0..array.len()).map(|i| array[&order.vector(i)]).collect()
+    }
+
+    #[test]
+    fn reshape_row_major() {
+        let array = MultiArray2::<i32>::new_by(Shape2::new([2, 3]), |i| i as i32);
+        let array = array.reshape(Shape3::new([3, 1, 2])).unwrap();
+        assert_eq!(array[&[1, 0, 0]], 2);
+        assert_eq!(array[&[2, 0, 1]], 5);
+        let view = array.reshape_view(Shape1::new([6])).unwrap();
+        assert_eq!(
+            view.iter().cloned().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn reshape_column_major() {
+        let column_major = || {
+            MultiArray2::<i32>::new_by(Shape2::new([2, 3]), |i| i as i32)
+                .to_layout(Layout::ColumnMajor)
+                .unwrap()
+        };
+        let reshaped = column_major().reshape(Shape2::new([3, 2])).unwrap();
+        assert_eq!(row_major_values(&reshaped), vec![0, 1, 2, 3, 4, 5]);
+        let reshaped = column_major()
+            .reshape(
+                Shape2::new([3, 2])
+                    .with_layout(Layout::ColumnMajor)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(reshaped[&[0, 1]], 1);
+        assert_eq!(reshaped[&[2, 0]], 4);
+        assert_eq!(row_major_values(&reshaped), vec![0, 1, 2, 3, 4, 5]);
+
+        assert!(matches!(
+            column_major().reshape_view(Shape1::new([6])),
+            Err(ReshapeError::LayoutMismatching(_))
+        ));
+    }
+
+    #[test]
+    fn reshape_length_mismatching() {
+        let array = MultiArray2::<i32>::new_by(Shape2::new([2, 3]), |i| i as i32);
+        assert!(matches!(
+            array.reshape_view(Shape2::new([4, 2])),
+            Err(ReshapeError::LengthMismatching(_))
+        ));
+        assert!(matches!(
+            array.reshape(Shape1::new([5])),
+            Err(ReshapeError::LengthMismatching(_))
+        ));
+    }
+
+    #[test]
+    fn permute_axes() {
+        let shape = Shape3::new([2, 3, 4])
+            .with_lower_bounds([1, 0, -1])
+            .with_names(["a", "b", "c"]);
+        let array = MultiArray3::<i32>::new_by(shape, |i| i as i32);
+        let view = array.permute_axes(&["c", "a", "b"]).unwrap();
+        assert_eq!(view.shape().shape(), &[4, 2, 3]);
+        assert_eq!(view.shape().lower_bounds(), &[-1, 1, 0]);
+        assert_eq!(view.shape().name_of_dimension(0), Some("c"));
+        assert_eq!(view.shape().name_of_dimension(2), Some("b"));
+        assert_eq!(view[&[-1, 1, 0]], 0);
+        assert_eq!(view[&[2, 2, 1]], 19);
+
+        let permuted = array.permuted_axes(&[2, 0, 1]).unwrap();
+        assert_eq!(permuted.shape().lower_bounds(), &[-1, 1, 0]);
+        assert_eq!(permuted.shape().name_of_dimension(1), Some("a"));
+        assert_eq!(
+            permuted.iter().take(8).cloned().collect::<Vec<_>>(),
+            vec![0, 4, 8, 12, 16, 20, 1, 5]
+        );
+    }
+
+    #[test]
+    fn transpose() {
+        let array = MultiArray2::<i32>::new_by(Shape2::new([2, 3]), |i| i as i32);
+        assert_eq!(array.transpose()[&[2, 1]], 5);
+        let transposed = array.transposed();
+        assert_eq!(transposed.shape().shape(), &[3, 2]);
+        assert_eq!(
+            transposed.iter().cloned().collect::<Vec<_>>(),
+            vec![0, 3, 1, 4, 2, 5]
+        );
+    }
+
+    #[test]
+    fn invalid_permutation() {
+        let array =
+            MultiArray3::<i32>::new_by(Shape3::new([2, 3, 4]).with_names(["a", "b", "c"]), |i| {
+                i as i32
+            });
+        for axes in [&[0usize, 0, 1][..], &[0, 1], &[0, 1, 3]] {
+            assert!(matches!(
+                array.permute_axes(axes),
+                Err(IndexCalculationError::DimensionMismatching(_))
+            ));
+        }
+        assert!(matches!(
+            array.permuted_axes(&["a", "x", "b"]),
+            Err(IndexCalculationError::UnknownDimension(_))
+        ));
+    }
+
+    fn values<S: Shape>(array: &MultiArray<i32, S>) -> Vec<i32> {
+        array.iter().cloned().collect()
+    }
+
+    #[test]
+    fn concatenate() {
+        let lhs = MultiArray2::<i32>::new_by(Shape2::new([2, 3]), |i| i as i32);
+        let rhs = MultiArray2::<i32>::new_by(Shape2::new([2, 1]), |i| 10 + i as i32);
+        let array = MultiArray2::concatenate(1, &[&lhs, &rhs]).unwrap();
+        assert_eq!(array.shape().shape(), &[2, 4]);
+        assert_eq!(values(&array), vec![0, 1, 2, 10, 3, 4, 5, 11]);
+
+        let lhs = MultiArray::<i32, DynShape>::new_by(DynShape::new(vec![1, 2]), |i| i as i32);
+        let rhs = MultiArray::<i32, DynShape>::new_by(DynShape::new(vec![2, 2]), |i| 10 + i as i32);
+        let array = MultiArray::concatenate(0, &[&lhs, &rhs]).unwrap();
+        assert_eq!(array.shape().shape(), &[3, 2]);
+        assert_eq!(values(&array), vec![0, 1, 10, 11, 12, 13]);
+    }
+
+    #[test]
+    fn stack() {
+        let lhs = MultiArray2::<i32>::new_by(Shape2::new([2, 2]), |i| i as i32);
+        let rhs = MultiArray2::<i32>::new_by(Shape2::new([2, 2]), |i| 10 + i as i32);
+        let array: MultiArray3<i32> = MultiArray2::stack(&[&lhs, &rhs, &lhs]).unwrap();
+        assert_eq!(array.shape().shape(), &[3, 2, 2]);
+        assert_eq!(array[&[1, 1, 0]], 12);
+        assert_eq!(array[&[2, 0, 1]], 1);
+
+        let lhs = MultiArray::<i32, DynShape>::new_by(DynShape::new(vec![3]), |i| i as i32);
+        let array = MultiArray::stack(&[&lhs, &lhs]).unwrap();
+        assert_eq!(array.shape().shape(), &[2, 3]);
+        assert_eq!(values(&array), vec![0, 1, 2, 0, 1, 2]);
+    }
+
+    #[test]
+    fn concatenate_column_major() {
+        let lhs = MultiArray2::<i32>::new_by(Shape2::new([2, 2]), |i| i as i32);
+        let rhs = MultiArray2::<i32>::new_by(Shape2::new([2, 2]), |i| 20 + i as i32)
+            .to_layout(Layout::ColumnMajor)
+            .unwrap();
+        let array = MultiArray2::concatenate(0, &[&lhs, &rhs]).unwrap();
+        assert_eq!(values(&array), vec![0, 1, 2, 3, 20, 21, 22, 23]);
+        let array = MultiArray2::concatenate(1, &[&rhs, &lhs]).unwrap();
+        assert_eq!(values(&array), vec![20, 21, 0, 1, 22, 23, 2, 3]);
+        let array = MultiArray2::stack(&[&lhs, &rhs]).unwrap();
+        assert_eq!(values(&array), vec![0, 1, 2, 3, 20, 21, 22, 23]);
+    }
+
+    #[test]
+    fn concatenate_incompatible() {
+        let lhs = MultiArray2::<i32>::new_by(Shape2::new([2, 3]), |i| i as i32);
+        let rhs = MultiArray2::<i32>::new_by(Shape2::new([3, 3]), |i| i as i32);
+        assert!(MultiArray2::concatenate(1, &[&lhs, &rhs]).is_err());
+        assert!(MultiArray2::concatenate(2, &[&lhs, &lhs]).is_err());
+        assert!(MultiArray2::stack(&[&lhs, &rhs]).is_err());
+
+        let lhs = MultiArray::<i32, DynShape>::new_by(DynShape::new(vec![2, 3]), |i| i as i32);
+        let rhs = MultiArray::<i32, DynShape>::new_by(DynShape::new(vec![2, 3, 1]), |i| i as i32);
+        assert!(MultiArray::concatenate(0, &[&lhs, &rhs]).is_err());
+        assert!(MultiArray::stack(&[&lhs, &rhs]).is_err());
+    }
+
+    #[test]
+    fn concatenate_nothing() {
+        assert!(MultiArray2::<i32>::concatenate(0, &[]).is_err());
+        assert!(MultiArray2::<i32>::stack(&[]).is_err());
+    }
+
+    #[test]
+    fn column_major() {
+        let shape = Shape2::new([2, 3]).with_lower_bounds([1, -1]);
+        let array = MultiArray2::<i32>::new_by(shape, |i| i as i32);
+        let columns = array.to_layout(Layout::ColumnMajor).unwrap();
+        assert_eq!(columns.shape().layout(), Layout::ColumnMajor);
+        assert_eq!(columns.shape().lower_bounds(), &[1, -1]);
+        for (vector, value) in array.indexed_iter() {
+            assert_eq!(columns[&vector], *value);
+            assert_eq!(columns.view()[&vector], *value);
+        }
+        assert_eq!(values(&columns), vec![0, 3, 1, 4, 2, 5]);
+        assert_eq!(
+            columns
+                .indexed_iter()
+                .map(|(vector, _)| vector)
+                .collect::<Vec<_>>(),
+            vec![[1, -1], [2, -1], [1, 0], [2, 0], [1, 1], [2, 1]]
+        );
+        for (vector, value) in columns.indexed_iter() {
+            assert_eq!(array[&vector], *value);
+        }
+
+        let rows = columns.to_layout(Layout::RowMajor).unwrap();
+        assert_eq!(rows.shape().layout(), Layout::RowMajor);
+        assert_eq!(values(&rows), values(&array));
+        assert_eq!(format!("{}", columns), format!("{}", array));
+    }
+
+    #[test]
+    fn dense_round_trip() {
+        let mut dense =
+            DenseMultiArray::<i32, Shape2>::new_dense_by(Shape2::new([2, 3]), |i| i as i32);
+        dense.as_mut_slice()[4] = 40;
+        assert_eq!(dense[&[1, 1]], 40);
+        let mut partial = dense.into_partial();
+        assert_eq!(values(&partial), vec![0, 1, 2, 3, 40, 5]);
+        partial.take(&[0, 2]).unwrap();
+        match partial.into_dense() {
+            Err(err) => assert_eq!(err.index, 2),
+            Ok(_) => panic!("an uninitialized cell should not convert"),
+        }
+
+        let partial = MultiArray2::<i32>::new_with(Shape2::new([2, 2]), 7);
+        let dense = DenseMultiArray::try_from(partial).unwrap();
+        assert_eq!(dense.as_slice(), &[7, 7, 7, 7]);
+        let partial = MultiArray2::from(dense);
+        assert!(partial.is_initialized(&[1, 1]));
+    }
+
+    #[test]
+    fn fallible_access() {
+        let mut array = MultiArray2::<i32>::new(Shape2::new([2, 3]));
+        assert_eq!(array.try_get(&[1, 2]).unwrap(), None);
+        assert_eq!(array.replace(&[1, 2], 5).unwrap(), None);
+        assert_eq!(array.replace(&[1, 2], 6).unwrap(), Some(5));
+        assert_eq!(array.try_get_at(5).unwrap(), Some(&6));
+        assert_eq!(array.take(&[1, 2]).unwrap(), Some(6));
+        assert_eq!(array.take(&[1, 2]).unwrap(), None);
+        assert!(!array.is_initialized(&[1, 2]));
+        assert!(!array.is_initialized(&[2, 0]));
+
+        assert!(matches!(
+            array.try_get(&[2, 0]),
+            Err(IndexCalculationError::OutOfShape(_))
+        ));
+        assert!(matches!(
+            array.set(&[0, 3], 1),
+            Err(IndexCalculationError::OutOfShape(_))
+        ));
+        assert!(matches!(
+            array.take_at(6),
+            Err(IndexCalculationError::OutOfShape(_))
+        ));
+        assert!(matches!(
+            array.try_get_mut_at(6),
+            Err(IndexCalculationError::OutOfShape(_))
+        ));
+        assert_eq!(array.iter().count(), 0);
+
+        let mut array = MultiArray::<i32, DynShape>::new(DynShape::new(vec![2, 3]));
+        assert!(matches!(
+            array.try_get(&vec![1]),
+            Err(IndexCalculationError::DimensionMismatching(_))
+        ));
+        assert!(matches!(
+            array.set(&vec![0, 1, 0], 1),
+            Err(IndexCalculationError::DimensionMismatching(_))
+        ));
+        assert!(matches!(
+            array.take(&vec![0, 1, 0]),
+            Err(IndexCalculationError::DimensionMismatching(_))
+        ));
+    }
+
+    #[test]
+    fn negative_indexes() {
+        let mut array = MultiArray2::<i32>::new_by(Shape2::new([2, 3]), |i| i as i32);
+        assert_eq!(array.try_get(&[-1, -1]).unwrap(), Some(&5));
+        assert_eq!(array.get(dummy!(-1, -1)).unwrap(), vec![Some(&5)]);
+        assert_eq!(array[&[-1, 0]], 3);
+        assert_eq!(array.view()[&[-2, -1]], 2);
+        assert_eq!(
+            array
+                .get_mut(dummy!(.., -1))
+                .unwrap()
+                .get(&vec![-1])
+                .unwrap(),
+            Some(&5)
+        );
+        array.set(&[-2, -3], 10).unwrap();
+        assert_eq!(array[&[0, 0]], 10);
+        assert!(array.try_get(&[-3, 0]).is_err());
+        assert!(array.get(dummy!(-3, 0)).is_err());
+
+        let shape = Shape2::new([2, 3]).with_lower_bounds([1, 0]);
+        let array = MultiArray2::<i32>::new_by(shape, |i| i as i32);
+        assert!(array.try_get(&[-1, 0]).is_err());
+        assert!(array.get(dummy!(-1, 0)).is_err());
+        assert_eq!(array.try_get(&[2, -1]).unwrap(), Some(&5));
+        assert_eq!(array.get(dummy!(2, -1)).unwrap(), vec![Some(&5)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not initialized")]
+    fn sparse_index_uninitialized() {
+        let array = SparseMultiArray::<i32, Shape1>::new_sparse(Shape1::new([2]));
+        let _ = array[1];
+    }
+}
@@ -0,0 +1,194 @@
+pub enum Entry<'a, T: Sized> {
+    Occupied(OccupiedEntry<'a, T>),
+    Vacant(VacantEntry<'a, T>),
+}
+
+pub struct OccupiedEntry<'a, T: Sized> {
+    pub(self) cell: &'a mut Option<T>,
+    pub(self) index: usize,
+}
+
+pub struct VacantEntry<'a, T: Sized> {
+    pub(self) cell: &'a mut Option<T>,
+    pub(self) index: usize,
+}
+
+impl<'a, T: Sized> Entry<'a, T> {
+    pub(crate) fn new(cell: &'a mut Option<T>, index: usize) -> Self {
+        if cell.is_some() {
+            Entry::Occupied(OccupiedEntry { cell, index })
+        } else {
+            Entry::Vacant(VacantEntry { cell, index })
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            Entry::Occupied(entry) => entry.index,
+            Entry::Vacant(entry) => entry.index,
+        }
+    }
+
+    pub fn or_insert(self, value: T) -> &'a mut T {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(value),
+        }
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> T>(self, f: F) -> &'a mut T {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(f()),
+        }
+    }
+
+    pub fn or_insert_with_index<F: FnOnce(usize) -> T>(self, f: F) -> &'a mut T {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = f(entry.index);
+                entry.insert(value)
+            }
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+
+    pub fn and_modify<F: FnOnce(&mut T)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, T: Sized> OccupiedEntry<'a, T> {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn get(&self) -> &T {
+        self.cell.as_ref().unwrap()
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.cell.as_mut().unwrap()
+    }
+
+    pub fn into_mut(self) -> &'a mut T {
+        self.cell.as_mut().unwrap()
+    }
+
+    pub fn insert(&mut self, value: T) -> T {
+        self.cell.replace(value).unwrap()
+    }
+
+    pub fn remove(self) -> T {
+        self.cell.take().unwrap()
+    }
+}
+
+impl<'a, T: Sized> VacantEntry<'a, T> {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn insert(self, value: T) -> &'a mut T {
+        self.cell.insert(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn occupied_and_vacant() {
+        let mut array = MultiArray2::<i32>::new(Shape2::new([2, 3]));
+        array.set(&[0, 1], 5).unwrap();
+        match array.entry(&[0, 1]).unwrap() {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.index(), 1);
+                assert_eq!(entry.insert(6), 5);
+                assert_eq!(*entry.get(), 6);
+            }
+            Entry::Vacant(_) => panic!("cell should be occupied"),
+        }
+        match array.entry(&[1, 2]).unwrap() {
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.index(), 5);
+                assert_eq!(*entry.insert(7), 7);
+            }
+            Entry::Occupied(_) => panic!("cell should be vacant"),
+        }
+        match array.entry_at(5).unwrap() {
+            Entry::Occupied(entry) => assert_eq!(entry.remove(), 7),
+            Entry::Vacant(_) => panic!("cell should be occupied"),
+        }
+        assert!(matches!(array.entry_at(5).unwrap(), Entry::Vacant(_)));
+    }
+
+    #[test]
+    fn or_insert() {
+        let mut array = MultiArray2::<i32>::new(Shape2::new([2, 3]));
+        *array.entry(&[1, 0]).unwrap().or_insert(1) += 10;
+        *array.entry(&[1, 0]).unwrap().or_insert(100) += 10;
+        assert_eq!(array[&[1, 0]], 21);
+
+        let mut calls = 0;
+        array.entry(&[0, 2]).unwrap().or_insert_with(|| {
+            calls += 1;
+            3
+        });
+        array.entry(&[0, 2]).unwrap().or_insert_with(|| {
+            calls += 1;
+            30
+        });
+        assert_eq!(calls, 1);
+        assert_eq!(array[&[0, 2]], 3);
+        assert_eq!(
+            *array
+                .entry_at(4)
+                .unwrap()
+                .or_insert_with_index(|i| i as i32),
+            4
+        );
+        assert_eq!(*array.entry_at(0).unwrap().or_default(), 0);
+    }
+
+    #[test]
+    fn and_modify() {
+        let mut array = MultiArray2::<i32>::new(Shape2::new([2, 3]));
+        array
+            .entry(&[0, 0])
+            .unwrap()
+            .and_modify(|value| *value += 1)
+            .or_insert(1);
+        array
+            .entry(&[0, 0])
+            .unwrap()
+            .and_modify(|value| *value += 1)
+            .or_insert(1);
+        assert_eq!(array[&[0, 0]], 2);
+    }
+
+    #[test]
+    fn out_of_shape() {
+        let mut array = MultiArray2::<i32>::new(Shape2::new([2, 3]));
+        assert!(matches!(
+            array.entry(&[2, 0]),
+            Err(IndexCalculationError::OutOfShape(_))
+        ));
+        assert!(matches!(
+            array.entry(&[0, 3]),
+            Err(IndexCalculationError::OutOfShape(_))
+        ));
+        assert!(array.entry_at(6).is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use crate::shape::row_major;
+use crate::*;
+use std::fmt;
+
+const EDGE_ITEMS: usize = 3;
+const THRESHOLD: usize = 1000;
+
+fn indexes_of(len: usize, summarized: bool) -> Vec<Option<usize>> {
+    if summarized && len > 2 * EDGE_ITEMS {
+        (0..EDGE_ITEMS)
+            .map(Option::Some)
+            .chain(std::iter::once(None))
+            .chain(((len - EDGE_ITEMS)..len).map(Option::Some))
+            .collect()
+    } else {
+        (0..len).map(Option::Some).collect()
+    }
+}
+
+struct ArrayFormatter<'b> {
+    lens: &'b [usize],
+    strides: Vec<usize>,
+    summarized: bool,
+}
+
+impl<'b> ArrayFormatter<'b> {
+    pub(self) fn new(lens: &'b [usize]) -> Self {
+        let mut strides = vec![1; lens.len()];
+        for i in (0..lens.len().saturating_sub(1)).rev() {
+            strides[i] = strides[i + 1] * lens[i + 1];
+        }
+        Self {
+            lens,
+            strides,
+            summarized: lens.iter().product::<usize>() > THRESHOLD,
+        }
+    }
+
+    pub(self) fn collect<F: FnMut(usize)>(&self, dimension: usize, base: usize, visit: &mut F) {
+        for i in indexes_of(self.lens[dimension], self.summarized)
+            .into_iter()
+            .flatten()
+        {
+            let index = base + i * self.strides[dimension];
+            if dimension + 1 == self.lens.len() {
+                visit(index);
+            } else {
+                self.collect(dimension + 1, index, visit);
+            }
+        }
+    }
+
+    pub(self) fn write<I: Iterator<Item = String>>(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        dimension: usize,
+        indent: usize,
+        width: usize,
+        cells: &mut I,
+    ) -> fmt::Result {
+        write!(f, "[")?;
+        let last = dimension + 1 == self.lens.len();
+        for (k, i) in indexes_of(self.lens[dimension], self.summarized)
+            .into_iter()
+            .enumerate()
+        {
+            if k != 0 {
+                if last {
+                    write!(f, ", ")?;
+                } else {
+                    write!(
+                        f,
+                        ",{}{}",
+                        "\n".repeat(self.lens.len() - dimension - 1),
+                        " ".repeat(indent + dimension + 1)
+                    )?;
+                }
+            }
+            match i {
+                Some(_) if last => {
+                    write!(f, "{:>width$}", cells.next().unwrap(), width = width)?;
+                }
+                Some(_) => {
+                    self.write(f, dimension + 1, indent, width, cells)?;
+                }
+                None => {
+                    write!(f, "...")?;
+                }
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+pub(crate) fn format_array<'a, T: 'a, C, R>(
+    f: &mut fmt::Formatter<'_>,
+    lens: &[usize],
+    indent: usize,
+    cell: C,
+    render: R,
+) -> fmt::Result
+where
+    C: Fn(usize) -> Option<&'a T>,
+    R: Fn(&T) -> String,
+{
+    let formatter = ArrayFormatter::new(lens);
+    let mut cells = Vec::new();
+    formatter.collect(0, 0, &mut |index| {
+        cells.push(match cell(index) {
+            Some(value) => render(value),
+            None => String::from("_"),
+        })
+    });
+    let width = cells
+        .iter()
+        .map(|cell| cell.chars().count())
+        .max()
+        .unwrap_or(0);
+    formatter.write(f, 0, indent, width, &mut cells.into_iter())
+}
+
+pub(crate) fn display_array<'a, T: fmt::Display + 'a, C>(
+    f: &mut fmt::Formatter<'_>,
+    lens: &[usize],
+    cell: C,
+) -> fmt::Result
+where
+    C: Fn(usize) -> Option<&'a T>,
+{
+    let precision = f.precision();
+    format_array(f, lens, 0, cell, |value| match precision {
+        Some(precision) => format!("{:.*}", precision, value),
+        None => format!("{}", value),
+    })
+}
+
+pub(crate) fn debug_array<'a, T: fmt::Debug + 'a, C>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    lens: &[usize],
+    cell: C,
+) -> fmt::Result
+where
+    C: Fn(usize) -> Option<&'a T>,
+{
+    let precision = f.precision();
+    write!(f, "{}(", name)?;
+    format_array(f, lens, name.len() + 1, cell, |value| match precision {
+        Some(precision) => format!("{:.*?}", precision, value),
+        None => format!("{:?}", value),
+    })?;
+    write!(f, ")")
+}
+
+impl<T: fmt::Display, S: Shape, St: Storage<T>> fmt::Display for MultiArray<T, S, St> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let logical = row_major(&self.shape);
+        display_array(f, self.shape.shape(), |index| {
+            self.list
+                .cell(self.shape.index(&logical.vector(index)).unwrap())
+        })
+    }
+}
+
+impl<T: fmt::Debug, S: Shape, St: Storage<T>> fmt::Debug for MultiArray<T, S, St> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let logical = row_major(&self.shape);
+        debug_array(f, "MultiArray", self.shape.shape(), |index| {
+            self.list
+                .cell(self.shape.index(&logical.vector(index)).unwrap())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn one_dimension() {
+        let array = MultiArray1::<i32>::new_by(Shape1::new([3]), |i| i as i32 + 1);
+        assert_eq!(format!("{}", array), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", array), "MultiArray([1, 2, 3])");
+        let array = MultiArray1::<f64>::new_by(Shape1::new([2]), |i| i as f64 / 3.0);
+        assert_eq!(format!("{:.2}", array), "[0.00, 0.33]");
+    }
+
+    #[test]
+    fn two_dimensions() {
+        let mut array = MultiArray2::<i32>::new(Shape2::new([2, 3]));
+        array.set(&[0, 0], 1).unwrap();
+        array.set(&[0, 2], 100).unwrap();
+        array.set(&[1, 0], -5).unwrap();
+        assert_eq!(format!("{}", array), "[[  1,   _, 100],\n [ -5,   _,   _]]");
+        assert_eq!(
+            format!("{:?}", array),
+            "MultiArray([[  1,   _, 100],\n            [ -5,   _,   _]])"
+        );
+    }
+
+    #[test]
+    fn three_dimensions() {
+        let array = MultiArray3::<i32>::new_by(Shape3::new([2, 2, 2]), |i| i as i32);
+        assert_eq!(
+            format!("{}", array),
+            "[[[0, 1],\n  [2, 3]],\n\n [[4, 5],\n  [6, 7]]]"
+        );
+    }
+
+    #[test]
+    fn summarized() {
+        let array = MultiArray2::<i32>::new_by(Shape2::new([2, 1000]), |i| (i % 1000) as i32);
+        assert_eq!(
+            format!("{}", array),
+            "[[  0,   1,   2, ..., 997, 998, 999],\n [  0,   1,   2, ..., 997, 998, 999]]"
+        );
+    }
+}
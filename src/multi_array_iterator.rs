@@ -0,0 +1,145 @@
+use crate::{Shape, Storage};
+
+pub struct MultiArrayIterator<'a, T: Sized + 'a, St: Storage<T> + 'a = Vec<Option<T>>> {
+    pub(self) iter: St::Iter<'a>,
+}
+
+impl<'a, T: Sized, St: Storage<T>> MultiArrayIterator<'a, T, St> {
+    pub(crate) fn new(list: &'a St) -> Self {
+        Self { iter: list.cells() }
+    }
+}
+
+impl<'a, T: Sized, St: Storage<T>> Iterator for MultiArrayIterator<'a, T, St> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, value)| value)
+    }
+}
+
+pub struct MultiArrayIteratorMut<'a, T: Sized + 'a, St: Storage<T> + 'a = Vec<Option<T>>> {
+    pub(self) iter: St::IterMut<'a>,
+}
+
+impl<'a, T: Sized, St: Storage<T>> MultiArrayIteratorMut<'a, T, St> {
+    pub(crate) fn new(list: &'a mut St) -> Self {
+        Self {
+            iter: list.cells_mut(),
+        }
+    }
+}
+
+impl<'a, T: Sized, St: Storage<T>> Iterator for MultiArrayIteratorMut<'a, T, St> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, value)| value)
+    }
+}
+
+pub struct MultiArrayIntoIterator<T: Sized, St: Storage<T> = Vec<Option<T>>> {
+    pub(self) iter: St::IntoIter,
+}
+
+impl<T: Sized, St: Storage<T>> MultiArrayIntoIterator<T, St> {
+    pub(crate) fn new(list: St) -> Self {
+        Self {
+            iter: list.into_cells(),
+        }
+    }
+}
+
+impl<T: Sized, St: Storage<T>> Iterator for MultiArrayIntoIterator<T, St> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, value)| value)
+    }
+}
+
+pub struct MultiArrayIndexedIterator<
+    'a,
+    T: Sized + 'a,
+    S: Shape,
+    St: Storage<T> + 'a = Vec<Option<T>>,
+> {
+    pub(self) iter: St::Iter<'a>,
+    pub(self) shape: &'a S,
+    pub(self) now: S::VectorType,
+    pub(self) next: usize,
+}
+
+impl<'a, T: Sized, S: Shape, St: Storage<T>> MultiArrayIndexedIterator<'a, T, S, St> {
+    pub(crate) fn new(list: &'a St, shape: &'a S) -> Self {
+        Self {
+            iter: list.cells(),
+            shape,
+            now: shape.zero(),
+            next: 0,
+        }
+    }
+}
+
+impl<'a, T: Sized, S: Shape, St: Storage<T>> Iterator for MultiArrayIndexedIterator<'a, T, S, St> {
+    type Item = (S::VectorType, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, value) = self.iter.next()?;
+        if index == self.next {
+            if index != 0 {
+                self.shape.next_vector(&mut self.now);
+            }
+        } else {
+            self.now = self.shape.vector(index);
+        }
+        self.next = index + 1;
+        Some((self.now.clone(), value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn indexed_iter_order() {
+        let shape = Shape2::new([2, 3]).with_lower_bounds([-1, 1]);
+        let array = MultiArray2::<i32>::new_by(shape, |i| i as i32);
+        assert_eq!(
+            array.indexed_iter().collect::<Vec<_>>(),
+            vec![
+                ([-1, 1], &0),
+                ([-1, 2], &1),
+                ([-1, 3], &2),
+                ([0, 1], &3),
+                ([0, 2], &4),
+                ([0, 3], &5)
+            ]
+        );
+        assert_eq!(
+            array.into_iter().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn indexed_iter_skips_uninitialized_cells() {
+        let mut array = MultiArray2::<i32>::new(Shape2::new([2, 3]));
+        array.set(&[0, 2], 2).unwrap();
+        array.set(&[1, 0], 3).unwrap();
+        array.set(&[1, 1], 4).unwrap();
+        let expected = vec![([0, 2], &2), ([1, 0], &3), ([1, 1], &4)];
+        assert_eq!(array.indexed_iter().collect::<Vec<_>>(), expected);
+
+        let mut sparse = SparseMultiArray::<i32, Shape2>::new_sparse(Shape2::new([2, 3]));
+        sparse.set(&[1, 1], 4).unwrap();
+        sparse.set(&[0, 2], 2).unwrap();
+        sparse.set(&[1, 0], 3).unwrap();
+        assert_eq!(sparse.indexed_iter().collect::<Vec<_>>(), expected);
+        for value in sparse.iter_mut() {
+            *value *= 10;
+        }
+        assert_eq!(sparse.iter().cloned().collect::<Vec<_>>(), vec![20, 30, 40]);
+    }
+}
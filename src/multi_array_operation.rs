@@ -0,0 +1,215 @@
+use crate::{IndexCalculationError, MultiArray, Shape};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+fn binary<T: Sized, S: Shape, F: Fn(&T, &T) -> T>(
+    lhs: &MultiArray<T, S>,
+    rhs: &MultiArray<T, S>,
+    operation: F,
+) -> Result<MultiArray<T, S>, IndexCalculationError> {
+    let shape = lhs.shape.broadcast(&rhs.shape)?;
+    let list = lhs
+        .broadcast_to(&shape)?
+        .cells()
+        .zip(rhs.broadcast_to(&shape)?.cells())
+        .map(|cells| match cells {
+            (Some(lhs), Some(rhs)) => Some(operation(lhs, rhs)),
+            _ => None,
+        })
+        .collect();
+    Ok(MultiArray {
+        list,
+        shape,
+        _marker: PhantomData,
+    })
+}
+
+fn binary_assign<T: Sized + Clone, S: Shape, F: Fn(&mut T, T)>(
+    lhs: &mut MultiArray<T, S>,
+    rhs: &MultiArray<T, S>,
+    operation: F,
+) -> Result<(), IndexCalculationError> {
+    let rhs = rhs.broadcast_to(&lhs.shape)?;
+    for (lhs, rhs) in lhs.list.iter_mut().zip(rhs.cells()) {
+        match (lhs.as_mut(), rhs) {
+            (Some(value), Some(rhs)) => operation(value, rhs.clone()),
+            _ => *lhs = None,
+        }
+    }
+    Ok(())
+}
+
+macro_rules! binary_operation {
+    ($op:ident, $func:ident, $assign_op:ident, $assign_func:ident, $try_assign_func:ident) => {
+        impl<'a, 'b, T: Sized + $op<Output = T> + Clone, S: Shape> $op<&'b MultiArray<T, S>>
+            for &'a MultiArray<T, S>
+        {
+            type Output = Result<MultiArray<T, S>, IndexCalculationError>;
+
+            fn $func(self, rhs: &'b MultiArray<T, S>) -> Self::Output {
+                binary(self, rhs, |lhs, rhs| lhs.clone().$func(rhs.clone()))
+            }
+        }
+
+        impl<T: Sized + $op<Output = T> + Clone, S: Shape> $op<MultiArray<T, S>> for MultiArray<T, S> {
+            type Output = Result<MultiArray<T, S>, IndexCalculationError>;
+
+            fn $func(self, rhs: MultiArray<T, S>) -> Self::Output {
+                (&self).$func(&rhs)
+            }
+        }
+
+        impl<'a, T: Sized + $op<Output = T> + Clone, S: Shape> $op<T> for &'a MultiArray<T, S> {
+            type Output = MultiArray<T, S>;
+
+            fn $func(self, rhs: T) -> Self::Output {
+                MultiArray {
+                    list: self
+                        .list
+                        .iter()
+                        .map(|cell| cell.as_ref().map(|value| value.clone().$func(rhs.clone())))
+                        .collect(),
+                    shape: self.shape.clone(),
+                    _marker: PhantomData,
+                }
+            }
+        }
+
+        impl<T: Sized + $op<Output = T> + Clone, S: Shape> $op<T> for MultiArray<T, S> {
+            type Output = MultiArray<T, S>;
+
+            fn $func(self, rhs: T) -> Self::Output {
+                MultiArray {
+                    list: self
+                        .list
+                        .into_iter()
+                        .map(|cell| cell.map(|value| value.$func(rhs.clone())))
+                        .collect(),
+                    shape: self.shape,
+                    _marker: PhantomData,
+                }
+            }
+        }
+
+        impl<T: Sized + $assign_op + Clone, S: Shape> MultiArray<T, S> {
+            pub fn $try_assign_func(
+                &mut self,
+                rhs: &MultiArray<T, S>,
+            ) -> Result<(), IndexCalculationError> {
+                binary_assign(self, rhs, |lhs, rhs| lhs.$assign_func(rhs))
+            }
+        }
+
+        /// # Panics
+        ///
+        /// Panics if `rhs` cannot be broadcast to the shape of `self`,
+        #[doc = concat!("use [`MultiArray::", stringify!($try_assign_func), "`] to get an error instead.")]
+        impl<'a, T: Sized + $assign_op + Clone, S: Shape> $assign_op<&'a MultiArray<T, S>>
+            for MultiArray<T, S>
+        {
+            fn $assign_func(&mut self, rhs: &'a MultiArray<T, S>) {
+                if let Err(err) = self.$try_assign_func(rhs) {
+                    panic!("{}", err);
+                }
+            }
+        }
+
+        impl<T: Sized + $assign_op + Clone, S: Shape> $assign_op<T> for MultiArray<T, S> {
+            fn $assign_func(&mut self, rhs: T) {
+                for value in self.iter_mut() {
+                    value.$assign_func(rhs.clone());
+                }
+            }
+        }
+    };
+}
+
+binary_operation!(Add, add, AddAssign, add_assign, try_add_assign);
+binary_operation!(Sub, sub, SubAssign, sub_assign, try_sub_assign);
+binary_operation!(Mul, mul, MulAssign, mul_assign, try_mul_assign);
+binary_operation!(Div, div, DivAssign, div_assign, try_div_assign);
+
+impl<T: Sized + Neg<Output = T> + Clone, S: Shape> Neg for &MultiArray<T, S> {
+    type Output = MultiArray<T, S>;
+
+    fn neg(self) -> Self::Output {
+        MultiArray {
+            list: self
+                .list
+                .iter()
+                .map(|cell| cell.as_ref().map(|value| -value.clone()))
+                .collect(),
+            shape: self.shape.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Sized + Neg<Output = T>, S: Shape> Neg for MultiArray<T, S> {
+    type Output = MultiArray<T, S>;
+
+    fn neg(self) -> Self::Output {
+        MultiArray {
+            list: self
+                .list
+                .into_iter()
+                .map(|cell| cell.map(|value| -value))
+                .collect(),
+            shape: self.shape,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn try_assign() {
+        let mut lhs = MultiArray2::<i32>::new_by(Shape2::new([2, 3]), |i| i as i32);
+        let rhs = MultiArray2::<i32>::new_by(Shape2::new([1, 3]), |i| i as i32);
+        lhs.try_add_assign(&rhs).unwrap();
+        assert_eq!(
+            lhs.iter().cloned().collect::<Vec<_>>(),
+            vec![0, 2, 4, 3, 5, 7]
+        );
+        let rhs = MultiArray2::<i32>::new_by(Shape2::new([2, 2]), |i| i as i32);
+        assert!(matches!(
+            lhs.try_sub_assign(&rhs),
+            Err(IndexCalculationError::ShapeMismatching(_))
+        ));
+        assert_eq!(
+            lhs.iter().cloned().collect::<Vec<_>>(),
+            vec![0, 2, 4, 3, 5, 7]
+        );
+    }
+
+    #[test]
+    fn shape_mismatching() {
+        let lhs = MultiArray2::<i32>::new_by(Shape2::new([2, 3]), |i| i as i32);
+        let rhs = MultiArray2::<i32>::new_by(Shape2::new([2, 2]), |i| i as i32);
+        match &lhs + &rhs {
+            Err(IndexCalculationError::ShapeMismatching(err)) => {
+                assert_eq!((err.dimension, err.len, err.broadcast_len), (1, 3, 2));
+            }
+            _ => panic!("shapes (2, 3) and (2, 2) should not broadcast"),
+        }
+        assert!(matches!(
+            lhs.broadcast_to(&Shape2::new([2, 4])),
+            Err(IndexCalculationError::ShapeMismatching(_))
+        ));
+        assert!(matches!(
+            lhs.broadcast_to(&Shape1::new([3])),
+            Err(IndexCalculationError::DimensionMismatching(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn assign_panics() {
+        let mut lhs = MultiArray2::<i32>::new_by(Shape2::new([2, 3]), |i| i as i32);
+        let rhs = MultiArray2::<i32>::new_by(Shape2::new([2, 2]), |i| i as i32);
+        lhs *= &rhs;
+    }
+}
@@ -0,0 +1,172 @@
+use crate::*;
+use rayon::iter::Flatten;
+use rayon::prelude::*;
+use rayon::slice::{Iter, IterMut};
+use std::iter::{Product, Sum};
+use std::marker::PhantomData;
+
+const PARALLEL_CHUNK_SIZE: usize = 4096;
+
+impl<T: Sized + Send + Sync, S: Shape + Sync> MultiArray<T, S> {
+    pub fn par_new_by<G>(shape: S, generator: G) -> Self
+    where
+        G: Fn(usize) -> T + Sync + Send,
+    {
+        Self {
+            list: (0..shape.len())
+                .into_par_iter()
+                .map(|index| Option::Some(generator(index)))
+                .collect(),
+            shape,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn par_iter(&self) -> Flatten<Iter<'_, Option<T>>> {
+        self.list.par_iter().flatten()
+    }
+
+    pub fn par_iter_mut(&mut self) -> Flatten<IterMut<'_, Option<T>>> {
+        self.list.par_iter_mut().flatten()
+    }
+
+    pub fn par_indexed_iter(&self) -> impl ParallelIterator<Item = (S::VectorType, &T)> + '_
+    where
+        S::VectorType: Send,
+    {
+        let shape = &self.shape;
+        self.list
+            .par_chunks(PARALLEL_CHUNK_SIZE)
+            .enumerate()
+            .flat_map_iter(move |(chunk, cells)| {
+                let mut vector = shape.vector(chunk * PARALLEL_CHUNK_SIZE);
+                cells.iter().enumerate().filter_map(move |(i, cell)| {
+                    if i != 0 {
+                        shape.next_vector(&mut vector);
+                    }
+                    cell.as_ref().map(|value| (vector.clone(), value))
+                })
+            })
+    }
+
+    pub fn par_fold_axis<'a, A: Axis, U, F>(
+        &'a self,
+        axis: A,
+        init: U,
+        fold: F,
+    ) -> Result<MultiArray<U, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        S::ReducedShape: Sync,
+        U: Clone + Send + Sync,
+        F: Fn(U, &'a T) -> U + Sync + Send,
+    {
+        MultiArrayReduction::new(self, false).par_fold_axis(axis, init, fold)
+    }
+
+    pub fn par_sum_axis<'a, A: Axis>(
+        &'a self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        S::ReducedShape: Sync,
+        T: Sum<&'a T>,
+    {
+        MultiArrayReduction::new(self, false).par_sum_axis(axis)
+    }
+
+    pub fn par_product_axis<'a, A: Axis>(
+        &'a self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        S::ReducedShape: Sync,
+        T: Product<&'a T>,
+    {
+        MultiArrayReduction::new(self, false).par_product_axis(axis)
+    }
+
+    pub fn par_min_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        S::ReducedShape: Sync,
+        T: PartialOrd + Clone,
+    {
+        MultiArrayReduction::new(self, false).par_min_axis(axis)
+    }
+
+    pub fn par_max_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        S::ReducedShape: Sync,
+        T: PartialOrd + Clone,
+    {
+        MultiArrayReduction::new(self, false).par_max_axis(axis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rayon::prelude::*;
+
+    fn records<S: Shape>(array: &MultiArray<i64, S>) -> Vec<(S::VectorType, i64)> {
+        array
+            .indexed_iter()
+            .map(|(vector, value)| (vector, *value))
+            .collect()
+    }
+
+    #[test]
+    fn same_as_serial() {
+        let array =
+            MultiArray3::<i64>::par_new_by(Shape3::new([3, 50, 100]), |i| (i as i64 * 7919) % 1013);
+        assert_eq!(
+            records(&array),
+            records(&MultiArray3::<i64>::new_by(
+                Shape3::new([3, 50, 100]),
+                |i| { (i as i64 * 7919) % 1013 }
+            ))
+        );
+        for axis in 0..3 {
+            assert_eq!(
+                records(&array.par_sum_axis(axis).unwrap()),
+                records(&array.sum_axis(axis).unwrap())
+            );
+            assert_eq!(
+                records(&array.par_min_axis(axis).unwrap()),
+                records(&array.min_axis(axis).unwrap())
+            );
+            assert_eq!(
+                records(&array.par_max_axis(axis).unwrap()),
+                records(&array.max_axis(axis).unwrap())
+            );
+        }
+        assert_eq!(array.par_iter().sum::<i64>(), array.iter().sum::<i64>());
+    }
+
+    #[test]
+    fn par_indexed_iter() {
+        let mut array = MultiArray3::<i64>::new_by(Shape3::new([3, 50, 100]), |i| i as i64);
+        for index in (0..array.len()).step_by(37) {
+            array.take_at(index).unwrap();
+        }
+        let serial: Vec<_> = array
+            .indexed_iter()
+            .map(|(vector, value)| (vector, *value))
+            .collect();
+        let parallel: Vec<_> = array
+            .par_indexed_iter()
+            .map(|(vector, value)| (vector, *value))
+            .collect();
+        assert_eq!(parallel, serial);
+    }
+}
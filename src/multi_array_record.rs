@@ -0,0 +1,305 @@
+use crate::*;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+pub enum RecordError {
+    Io(io::Error),
+    Parse {
+        line: usize,
+        message: String,
+    },
+    DimensionMismatching {
+        line: usize,
+        err: DimensionMismatchingError,
+    },
+    OutOfShape {
+        line: usize,
+        err: OutOfShapeError,
+    },
+    UnknownDimension {
+        line: usize,
+        err: UnknownDimensionError,
+    },
+}
+
+impl RecordError {
+    pub(crate) fn from_index_error(line: usize, err: IndexCalculationError) -> Self {
+        match err {
+            IndexCalculationError::DimensionMismatching(err) => {
+                RecordError::DimensionMismatching { line, err }
+            }
+            IndexCalculationError::OutOfShape(err) => RecordError::OutOfShape { line, err },
+            IndexCalculationError::UnknownDimension(err) => {
+                RecordError::UnknownDimension { line, err }
+            }
+            IndexCalculationError::InvalidStep(err) => RecordError::Parse {
+                line,
+                message: err.to_string(),
+            },
+            IndexCalculationError::ShapeMismatching(err) => RecordError::Parse {
+                line,
+                message: err.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Io(err) => {
+                write!(f, "{}", err)
+            }
+            RecordError::Parse { line, message } => {
+                write!(f, "Line {}: {}.", line, message)
+            }
+            RecordError::DimensionMismatching { line, err } => {
+                write!(f, "Line {}: {}", line, err)
+            }
+            RecordError::OutOfShape { line, err } => {
+                write!(f, "Line {}: {}", line, err)
+            }
+            RecordError::UnknownDimension { line, err } => {
+                write!(f, "Line {}: {}", line, err)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl From<io::Error> for RecordError {
+    fn from(err: io::Error) -> Self {
+        RecordError::Io(err)
+    }
+}
+
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// spaces around a field are dropped, but never the ones inside its quotes
+fn split(line: &str) -> Result<Vec<String>, String> {
+    let mut ret = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut was_quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    quoted = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' {
+            if !was_quoted {
+                field = field.trim_start().to_string();
+            }
+            quoted = true;
+            was_quoted = true;
+        } else if c == ',' {
+            ret.push(if was_quoted {
+                field.clone()
+            } else {
+                field.trim().to_string()
+            });
+            field.clear();
+            was_quoted = false;
+        } else if !(was_quoted && c.is_whitespace()) {
+            field.push(c);
+        }
+    }
+    if quoted {
+        return Err(String::from("unterminated quoted field"));
+    }
+    ret.push(if was_quoted {
+        field
+    } else {
+        field.trim().to_string()
+    });
+    Ok(ret)
+}
+
+fn record_of(text: &str) -> &str {
+    match text.strip_suffix('\n') {
+        Some(text) => text.strip_suffix('\r').unwrap_or(text),
+        None => text,
+    }
+}
+
+pub fn write_csv<W, T, S, St>(
+    writer: &mut W,
+    array: &MultiArray<T, S, St>,
+    names: Option<&[&str]>,
+) -> Result<(), RecordError>
+where
+    W: Write,
+    T: fmt::Display,
+    S: Shape,
+    St: Storage<T>,
+{
+    let dimension = array.shape.dimension();
+    if let Some(names) = names {
+        if names.len() != dimension {
+            return Err(RecordError::DimensionMismatching {
+                line: 1,
+                err: DimensionMismatchingError {
+                    dimension,
+                    vector_dimension: names.len(),
+                },
+            });
+        }
+        let mut header: Vec<String> = names.iter().map(|name| escape(name)).collect();
+        header.push(String::from("value"));
+        writeln!(writer, "{}", header.join(","))?;
+    }
+    for (vector, value) in array.to_records() {
+        let mut fields: Vec<String> = (0..dimension).map(|i| vector[i].to_string()).collect();
+        fields.push(escape(&value.to_string()));
+        writeln!(writer, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+pub fn read_csv<R, T, S>(
+    mut reader: R,
+    shape: S,
+    has_header: bool,
+) -> Result<MultiArray<T, S>, RecordError>
+where
+    R: BufRead,
+    T: FromStr,
+    S: Shape,
+{
+    let dimension = shape.dimension();
+    let mut ret = MultiArray::new(shape);
+    let mut header = has_header;
+    let mut text = String::new();
+    let mut read = 0;
+    loop {
+        text.clear();
+        if reader.read_line(&mut text)? == 0 {
+            break;
+        }
+        read += 1;
+        let line = read;
+        if text.trim().is_empty() {
+            continue;
+        }
+        // a quoted field may hold line breaks, so a record goes on until its quotes are closed,
+        // and only the terminator after them is dropped
+        let fields = loop {
+            match split(record_of(&text)) {
+                Ok(fields) => break fields,
+                Err(message) => {
+                    if reader.read_line(&mut text)? == 0 {
+                        return Err(RecordError::Parse { line, message });
+                    }
+                    read += 1;
+                }
+            }
+        };
+        if header {
+            header = false;
+            continue;
+        }
+        if fields.len() != dimension + 1 {
+            return Err(RecordError::DimensionMismatching {
+                line,
+                err: DimensionMismatchingError {
+                    dimension,
+                    vector_dimension: fields.len() - 1,
+                },
+            });
+        }
+        let mut vector = ret.shape.zero();
+        for i in 0..dimension {
+            vector[i] = match fields[i].parse::<isize>() {
+                Ok(index) => index,
+                Err(_) => {
+                    return Err(RecordError::Parse {
+                        line,
+                        message: format!("invalid index {}", fields[i]),
+                    });
+                }
+            };
+        }
+        let value = match fields[dimension].parse::<T>() {
+            Ok(value) => value,
+            Err(_) => {
+                return Err(RecordError::Parse {
+                    line,
+                    message: format!("invalid value {}", fields[dimension]),
+                });
+            }
+        };
+        let index = ret
+            .shape
+            .index(&vector)
+            .map_err(|err| RecordError::from_index_error(line, err))?;
+        ret.list[index] = Some(value);
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_fields() {
+        assert_eq!(
+            split(r#" 0 , " a, ""b"" " ,c"#).unwrap(),
+            vec!["0", " a, \"b\" ", "c"]
+        );
+        assert!(split(r#"0,"a"#).is_err());
+    }
+
+    #[test]
+    fn multi_line_round_trip() {
+        let array = MultiArray1::<String>::new_by(Shape1::new([2]), |i| {
+            [" first\nline ", "second\r\n\nline"][i].to_string()
+        });
+        let mut bytes = Vec::new();
+        write_csv(&mut bytes, &array, Some(&["index\nname"])).unwrap();
+        let other: MultiArray1<String> =
+            read_csv(bytes.as_slice(), Shape1::new([2]), true).unwrap();
+        assert_eq!(other[&[0]], " first\nline ");
+        assert_eq!(other[&[1]], "second\r\n\nline");
+    }
+
+    #[test]
+    fn crlf_records() {
+        let text = "index,value\r\n0,\"x\r\ny\"\r\n1, z \r\n";
+        let array: MultiArray1<String> = read_csv(text.as_bytes(), Shape1::new([2]), true).unwrap();
+        assert_eq!(array[&[0]], "x\r\ny");
+        assert_eq!(array[&[1]], "z");
+        let err = read_csv::<_, String, _>("0,\"a\nb\"\nx,c\n".as_bytes(), Shape1::new([2]), false)
+            .err()
+            .unwrap();
+        assert!(matches!(err, RecordError::Parse { line: 3, .. }));
+    }
+
+    #[test]
+    fn unterminated_record() {
+        let err = read_csv::<_, String, _>("0,a\n1,\"b\n".as_bytes(), Shape1::new([2]), false)
+            .err()
+            .unwrap();
+        assert!(matches!(err, RecordError::Parse { line: 2, .. }));
+    }
+}
@@ -0,0 +1,339 @@
+use crate::{
+    Axis, DimensionMismatchingError, MultiArray, ReducibleShape, Shape, UninitializedError,
+    UnknownDimensionError,
+};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::{Flatten, Product, StepBy, Sum};
+use std::marker::PhantomData;
+use std::slice;
+
+#[derive(Debug, Clone)]
+pub enum ReductionError {
+    DimensionMismatching(DimensionMismatchingError),
+    Uninitialized(UninitializedError),
+    UnknownDimension(UnknownDimensionError),
+}
+
+impl fmt::Display for ReductionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReductionError::DimensionMismatching(err) => {
+                write!(f, "{}", err)
+            }
+            ReductionError::Uninitialized(err) => {
+                write!(f, "{}", err)
+            }
+            ReductionError::UnknownDimension(err) => {
+                write!(f, "{}", err)
+            }
+        }
+    }
+}
+
+pub type LineIterator<'a, T> = Flatten<StepBy<slice::Iter<'a, Option<T>>>>;
+
+pub struct MultiArrayReduction<'a, T: Sized, S: Shape> {
+    pub(self) array: &'a MultiArray<T, S>,
+    pub(self) strict: bool,
+}
+
+impl<'a, T: Sized, S: Shape> MultiArrayReduction<'a, T, S> {
+    pub(crate) fn new(array: &'a MultiArray<T, S>, strict: bool) -> Self {
+        Self { array, strict }
+    }
+
+    pub(self) fn check(&self) -> Result<(), ReductionError> {
+        if self.strict {
+            if let Some(index) = self.array.list.iter().position(|cell| cell.is_none()) {
+                return Err(ReductionError::Uninitialized(UninitializedError { index }));
+            }
+        }
+        Ok(())
+    }
+
+    pub(self) fn dimension_of<A: Axis>(&self, axis: A) -> Result<usize, ReductionError> {
+        axis.dimension_of(&self.array.shape)
+            .map_err(ReductionError::UnknownDimension)
+    }
+
+    pub(self) fn line_of(
+        &self,
+        dimension: usize,
+        vector: &<S::ReducedShape as Shape>::VectorType,
+    ) -> LineIterator<'a, T>
+    where
+        S: ReducibleShape,
+    {
+        let len = self.array.shape.shape()[dimension];
+        let stride = self.array.shape.offset()[dimension];
+        let offset = self.array.shape.offset();
+        let lower_bounds = self.array.shape.lower_bounds();
+        let mut base = 0;
+        for j in 0..(self.array.shape.dimension() - 1) {
+            let k = if j < dimension { j } else { j + 1 };
+            base += ((vector[j] - lower_bounds[k]) as usize) * offset[k];
+        }
+        if len == 0 {
+            return self.array.list[..0].iter().step_by(1).flatten();
+        }
+        let end = base + (len - 1) * stride + 1;
+        self.array.list[base..end].iter().step_by(stride).flatten()
+    }
+
+    pub(self) fn reduce_lines<U, F>(
+        &self,
+        dimension: usize,
+        mut reduction: F,
+    ) -> Result<MultiArray<U, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        F: FnMut(LineIterator<'a, T>) -> Option<U>,
+    {
+        let shape = self
+            .array
+            .shape
+            .reduce(dimension)
+            .map_err(ReductionError::DimensionMismatching)?;
+        self.check()?;
+        let mut list = Vec::with_capacity(shape.len());
+        let mut vector = shape.zero();
+        for i in 0..shape.len() {
+            if i != 0 {
+                shape.next_vector(&mut vector);
+            }
+            list.push(reduction(self.line_of(dimension, &vector)));
+        }
+        Ok(MultiArray {
+            list,
+            shape,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn fold_axis<A: Axis, U, F>(
+        &self,
+        axis: A,
+        init: U,
+        fold: F,
+    ) -> Result<MultiArray<U, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        U: Clone,
+        F: Fn(U, &'a T) -> U,
+    {
+        self.reduce_lines(self.dimension_of(axis)?, |line| {
+            Some(line.fold(init.clone(), &fold))
+        })
+    }
+
+    pub fn sum_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        T: Sum<&'a T>,
+    {
+        self.reduce_lines(self.dimension_of(axis)?, |line| Some(line.sum()))
+    }
+
+    pub fn product_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        T: Product<&'a T>,
+    {
+        self.reduce_lines(self.dimension_of(axis)?, |line| Some(line.product()))
+    }
+
+    pub fn min_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        T: PartialOrd + Clone,
+    {
+        self.reduce_lines(self.dimension_of(axis)?, |line| min_of(line).cloned())
+    }
+
+    pub fn max_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        T: PartialOrd + Clone,
+    {
+        self.reduce_lines(self.dimension_of(axis)?, |line| max_of(line).cloned())
+    }
+
+    pub fn fold<U, F>(&self, init: U, fold: F) -> Result<U, ReductionError>
+    where
+        F: Fn(U, &'a T) -> U,
+    {
+        self.check()?;
+        Ok(self.array.list.iter().flatten().fold(init, fold))
+    }
+
+    pub fn sum(&self) -> Result<T, ReductionError>
+    where
+        T: Sum<&'a T>,
+    {
+        self.check()?;
+        Ok(self.array.list.iter().flatten().sum())
+    }
+
+    pub fn product(&self) -> Result<T, ReductionError>
+    where
+        T: Product<&'a T>,
+    {
+        self.check()?;
+        Ok(self.array.list.iter().flatten().product())
+    }
+
+    pub fn min(&self) -> Result<Option<&'a T>, ReductionError>
+    where
+        T: PartialOrd,
+    {
+        self.check()?;
+        Ok(min_of(self.array.list.iter().flatten()))
+    }
+
+    pub fn max(&self) -> Result<Option<&'a T>, ReductionError>
+    where
+        T: PartialOrd,
+    {
+        self.check()?;
+        Ok(max_of(self.array.list.iter().flatten()))
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T: Sized + Sync, S: Shape + Sync> MultiArrayReduction<'a, T, S> {
+    pub(self) fn par_reduce_lines<U, F>(
+        &self,
+        dimension: usize,
+        reduction: F,
+    ) -> Result<MultiArray<U, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        S::ReducedShape: Sync,
+        U: Send,
+        F: Fn(LineIterator<'a, T>) -> Option<U> + Sync + Send,
+    {
+        let shape = self
+            .array
+            .shape
+            .reduce(dimension)
+            .map_err(ReductionError::DimensionMismatching)?;
+        self.check()?;
+        let list = (0..shape.len())
+            .into_par_iter()
+            .map(|i| reduction(self.line_of(dimension, &shape.vector(i))))
+            .collect();
+        Ok(MultiArray {
+            list,
+            shape,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn par_fold_axis<A: Axis, U, F>(
+        &self,
+        axis: A,
+        init: U,
+        fold: F,
+    ) -> Result<MultiArray<U, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        S::ReducedShape: Sync,
+        U: Clone + Send + Sync,
+        F: Fn(U, &'a T) -> U + Sync + Send,
+    {
+        self.par_reduce_lines(self.dimension_of(axis)?, |line| {
+            Some(line.fold(init.clone(), &fold))
+        })
+    }
+
+    pub fn par_sum_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        S::ReducedShape: Sync,
+        T: Sum<&'a T> + Send,
+    {
+        self.par_reduce_lines(self.dimension_of(axis)?, |line| Some(line.sum()))
+    }
+
+    pub fn par_product_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        S::ReducedShape: Sync,
+        T: Product<&'a T> + Send,
+    {
+        self.par_reduce_lines(self.dimension_of(axis)?, |line| Some(line.product()))
+    }
+
+    pub fn par_min_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        S::ReducedShape: Sync,
+        T: PartialOrd + Clone + Send,
+    {
+        self.par_reduce_lines(self.dimension_of(axis)?, |line| min_of(line).cloned())
+    }
+
+    pub fn par_max_axis<A: Axis>(
+        &self,
+        axis: A,
+    ) -> Result<MultiArray<T, S::ReducedShape>, ReductionError>
+    where
+        S: ReducibleShape,
+        S::ReducedShape: Sync,
+        T: PartialOrd + Clone + Send,
+    {
+        self.par_reduce_lines(self.dimension_of(axis)?, |line| max_of(line).cloned())
+    }
+}
+
+fn min_of<'a, T: PartialOrd>(iter: impl Iterator<Item = &'a T>) -> Option<&'a T> {
+    iter.fold(None, |min, value| match min {
+        Some(min) if value.partial_cmp(min) != Some(Ordering::Less) => Some(min),
+        _ => Some(value),
+    })
+}
+
+fn max_of<'a, T: PartialOrd>(iter: impl Iterator<Item = &'a T>) -> Option<&'a T> {
+    iter.fold(None, |max, value| match max {
+        Some(max) if value.partial_cmp(max) != Some(Ordering::Greater) => Some(max),
+        _ => Some(value),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extremes() {
+        let values = [3.0, 1.0, f64::NAN, 4.0, 1.0];
+        assert_eq!(min_of(values.iter()), Some(&1.0));
+        assert_eq!(max_of(values.iter()), Some(&4.0));
+        assert!(min_of([f64::NAN, 1.0].iter()).unwrap().is_nan());
+        assert_eq!(max_of(std::iter::empty::<&i32>()), None);
+    }
+}
@@ -1,7 +1,722 @@
-use crate::{MultiArray, Shape};
+use crate::multi_array_format::{debug_array, display_array};
+use crate::shape::{row_major, with_lower_bounds, with_names};
+use crate::{IndexCalculationError, MultiArray, OutOfShapeError, Shape};
+use std::fmt;
+use std::iter::Flatten;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut, Range};
+
+#[derive(Clone)]
+pub(crate) struct ViewAxis {
+    pub(crate) stride: isize,
+    pub(crate) indexes: Option<Vec<usize>>,
+}
+
+impl ViewAxis {
+    pub(crate) fn new(stride: isize) -> Self {
+        Self {
+            stride,
+            indexes: None,
+        }
+    }
+
+    pub(crate) fn offset_of(&self, index: usize) -> isize {
+        match &self.indexes {
+            Some(indexes) => self.stride * (indexes[index] as isize),
+            None => self.stride * (index as isize),
+        }
+    }
+}
+
+pub(crate) fn locate<S: Shape>(
+    shape: &S,
+    base: usize,
+    axes: &[ViewAxis],
+    vector: &S::VectorType,
+) -> usize {
+    let mut index = base as isize;
+    for (i, axis) in axes.iter().enumerate() {
+        index += axis.offset_of((vector[i] - shape.lower_bounds()[i]) as usize);
+    }
+    index as usize
+}
+
+pub(crate) fn flat_index_of<S: Shape>(
+    shape: &S,
+    base: usize,
+    axes: &[ViewAxis],
+    vector: &S::VectorType,
+) -> Result<usize, IndexCalculationError> {
+    shape.index(vector)?;
+    let mut index = base as isize;
+    for (i, axis) in axes.iter().enumerate() {
+        index += axis.offset_of(shape.actual_index(i, vector[i]).unwrap());
+    }
+    Ok(index as usize)
+}
+
+pub(crate) fn check_split<S: Shape>(
+    shape: &S,
+    dimension: usize,
+    index: usize,
+) -> Result<usize, IndexCalculationError> {
+    let len = shape
+        .len_of_dimension(dimension)
+        .map_err(IndexCalculationError::DimensionMismatching)?;
+    if index > len {
+        return Err(IndexCalculationError::OutOfShape(OutOfShapeError {
+            dimension,
+            name: shape.names()[dimension].clone(),
+            lower_bound: 0,
+            len,
+            vector_index: index as isize,
+        }));
+    }
+    Ok(len)
+}
+
+pub(crate) fn chunk_ranges<S: Shape>(
+    shape: &S,
+    dimension: usize,
+    size: usize,
+) -> Result<Vec<Range<usize>>, IndexCalculationError> {
+    let len = check_split(shape, dimension, 0)?;
+    if size == 0 {
+        return Err(IndexCalculationError::OutOfShape(OutOfShapeError {
+            dimension,
+            name: shape.names()[dimension].clone(),
+            lower_bound: 0,
+            len,
+            vector_index: 0,
+        }));
+    }
+    Ok((0..len)
+        .step_by(size)
+        .map(|start| start..std::cmp::min(start + size, len))
+        .collect())
+}
+
+pub(crate) fn split_layout<S: Shape>(
+    shape: &S,
+    base: usize,
+    axes: &[ViewAxis],
+    dimension: usize,
+    range: Range<usize>,
+) -> (S, usize, Vec<ViewAxis>) {
+    let mut lens = shape.shape().to_vec();
+    lens[dimension] = range.len();
+    // a split keeps the logical indexes of the cells it takes
+    let mut lower_bounds = shape.lower_bounds().to_vec();
+    lower_bounds[dimension] += range.start as isize;
+    let mut axes = axes.to_vec();
+    let stride = axes[dimension].stride;
+    let base = match &mut axes[dimension].indexes {
+        Some(indexes) => {
+            *indexes = indexes[range].to_vec();
+            base
+        }
+        None => (base as isize + stride * range.start as isize) as usize,
+    };
+    (
+        with_lower_bounds(
+            with_names(S::from_slice(&lens).unwrap(), shape.names()),
+            &lower_bounds,
+        ),
+        base,
+        axes,
+    )
+}
+
+pub type SplitView<'a, T, S> = (MultiArrayView<'a, T, S>, MultiArrayView<'a, T, S>);
+pub type SplitViewMut<'a, T, S> = (MultiArrayViewMut<'a, T, S>, MultiArrayViewMut<'a, T, S>);
 
 pub struct MultiArrayView<'a, T: Sized, S: Shape> {
-    pub(self) parent: &'a MultiArray<T, S>,
-    pub(self) list: Vec<Vec<Option<&'a T>>>,
+    pub(self) list: &'a [Option<T>],
     pub(self) shape: S,
+    pub(self) base: usize,
+    pub(self) axes: Vec<ViewAxis>,
+}
+
+impl<'a, T: Sized, S: Shape> MultiArrayView<'a, T, S> {
+    pub(crate) fn new(list: &'a [Option<T>], shape: S, base: usize, axes: Vec<ViewAxis>) -> Self {
+        Self {
+            list,
+            shape,
+            base,
+            axes,
+        }
+    }
+
+    pub fn shape(&self) -> &S {
+        &self.shape
+    }
+
+    pub fn len(&self) -> usize {
+        self.shape.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, vector: &S::VectorType) -> Result<Option<&'a T>, IndexCalculationError> {
+        let index = flat_index_of(&self.shape, self.base, &self.axes, vector)?;
+        Ok(self.list[index].as_ref())
+    }
+
+    pub fn iter(&self) -> Flatten<MultiArrayViewIterator<'a, '_, T, S>> {
+        self.cells().flatten()
+    }
+
+    pub fn cells(&self) -> MultiArrayViewIterator<'a, '_, T, S> {
+        MultiArrayViewIterator::new(self.list.as_ptr(), &self.shape, self.base, &self.axes)
+    }
+
+    pub(self) fn sub_view(&self, dimension: usize, range: Range<usize>) -> Self {
+        let (shape, base, axes) =
+            split_layout(&self.shape, self.base, &self.axes, dimension, range);
+        Self::new(self.list, shape, base, axes)
+    }
+
+    pub fn split_at(
+        &self,
+        dimension: usize,
+        index: usize,
+    ) -> Result<(Self, Self), IndexCalculationError> {
+        let len = check_split(&self.shape, dimension, index)?;
+        Ok((
+            self.sub_view(dimension, 0..index),
+            self.sub_view(dimension, index..len),
+        ))
+    }
+
+    pub fn chunks(
+        &self,
+        dimension: usize,
+        size: usize,
+    ) -> Result<Vec<Self>, IndexCalculationError> {
+        Ok(chunk_ranges(&self.shape, dimension, size)?
+            .into_iter()
+            .map(|range| self.sub_view(dimension, range))
+            .collect())
+    }
+
+    pub fn to_multi_array(&self) -> MultiArray<T, S>
+    where
+        T: Clone,
+    {
+        MultiArray {
+            list: self.cells().cloned().collect(),
+            shape: self.shape.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+pub struct MultiArrayViewIterator<'a, 'b, T: Sized, S: Shape> {
+    pub(self) list: *const Option<T>,
+    pub(self) shape: &'b S,
+    pub(self) base: usize,
+    pub(self) axes: &'b [ViewAxis],
+    pub(self) now: S::VectorType,
+    pub(self) rest: usize,
+    pub(self) _marker: PhantomData<&'a Option<T>>,
+}
+
+impl<'a, 'b, T: Sized, S: Shape> MultiArrayViewIterator<'a, 'b, T, S> {
+    pub(self) fn new(
+        list: *const Option<T>,
+        shape: &'b S,
+        base: usize,
+        axes: &'b [ViewAxis],
+    ) -> Self {
+        Self {
+            list,
+            shape,
+            base,
+            axes,
+            now: shape.zero(),
+            rest: shape.len(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl<'a, 'b, T: Sized + Sync, S: Shape + Sync> Send for MultiArrayViewIterator<'a, 'b, T, S> where
+    S::VectorType: Send
+{
+}
+
+impl<'a, 'b, T: Sized, S: Shape> Iterator for MultiArrayViewIterator<'a, 'b, T, S> {
+    type Item = &'a Option<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest == 0 {
+            return None;
+        }
+        let index = locate(self.shape, self.base, self.axes, &self.now);
+        self.rest -= 1;
+        if self.rest != 0 {
+            self.shape.next_vector(&mut self.now);
+        }
+        unsafe { Some(&*self.list.add(index)) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.rest, Some(self.rest))
+    }
+}
+
+impl<'a, T: Sized, S: Shape> Index<usize> for MultiArrayView<'a, T, S> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self[&self.shape.vector(index)]
+    }
+}
+
+impl<'a, T: Sized, S: Shape> Index<&S::VectorType> for MultiArrayView<'a, T, S> {
+    type Output = T;
+
+    fn index(&self, vector: &S::VectorType) -> &Self::Output {
+        match flat_index_of(&self.shape, self.base, &self.axes, vector) {
+            Ok(index) => match &self.list[index] {
+                Some(value) => value,
+                None => {
+                    panic!(
+                        "Element with index {} in the multi-array is not initialized",
+                        index
+                    )
+                }
+            },
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+pub struct MultiArrayViewMut<'a, T: Sized, S: Shape> {
+    pub(self) list: *mut Option<T>,
+    pub(self) shape: S,
+    pub(self) base: usize,
+    pub(self) axes: Vec<ViewAxis>,
+    pub(self) _marker: PhantomData<&'a mut [Option<T>]>,
+}
+
+unsafe impl<'a, T: Sized + Send, S: Shape + Send> Send for MultiArrayViewMut<'a, T, S> {}
+unsafe impl<'a, T: Sized + Sync, S: Shape + Sync> Sync for MultiArrayViewMut<'a, T, S> {}
+
+impl<'a, T: Sized, S: Shape> MultiArrayViewMut<'a, T, S> {
+    pub(crate) fn new(
+        list: &'a mut [Option<T>],
+        shape: S,
+        base: usize,
+        axes: Vec<ViewAxis>,
+    ) -> Self {
+        Self {
+            list: list.as_mut_ptr(),
+            shape,
+            base,
+            axes,
+            _marker: PhantomData,
+        }
+    }
+
+    // the caller guarantees that the cells reached by the layout are borrowed by this view only
+    pub(crate) unsafe fn from_raw(
+        list: *mut Option<T>,
+        shape: S,
+        base: usize,
+        axes: Vec<ViewAxis>,
+    ) -> Self {
+        Self {
+            list,
+            shape,
+            base,
+            axes,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(self) fn cell(&self, index: usize) -> &Option<T> {
+        unsafe { &*self.list.add(index) }
+    }
+
+    pub(self) fn cell_mut(&mut self, index: usize) -> &mut Option<T> {
+        unsafe { &mut *self.list.add(index) }
+    }
+
+    pub fn shape(&self) -> &S {
+        &self.shape
+    }
+
+    pub fn len(&self) -> usize {
+        self.shape.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, vector: &S::VectorType) -> Result<Option<&T>, IndexCalculationError> {
+        let index = flat_index_of(&self.shape, self.base, &self.axes, vector)?;
+        Ok(self.cell(index).as_ref())
+    }
+
+    pub fn get_mut(
+        &mut self,
+        vector: &S::VectorType,
+    ) -> Result<&mut Option<T>, IndexCalculationError> {
+        let index = flat_index_of(&self.shape, self.base, &self.axes, vector)?;
+        Ok(self.cell_mut(index))
+    }
+
+    pub fn iter(&self) -> Flatten<MultiArrayViewIterator<'_, '_, T, S>> {
+        self.cells().flatten()
+    }
+
+    pub fn cells(&self) -> MultiArrayViewIterator<'_, '_, T, S> {
+        MultiArrayViewIterator::new(self.list, &self.shape, self.base, &self.axes)
+    }
+
+    pub fn iter_mut(&mut self) -> Flatten<MultiArrayViewIteratorMut<'_, T, S>> {
+        self.cells_mut().flatten()
+    }
+
+    pub fn cells_mut(&mut self) -> MultiArrayViewIteratorMut<'_, T, S> {
+        MultiArrayViewIteratorMut {
+            list: self.list,
+            shape: &self.shape,
+            base: self.base,
+            axes: &self.axes,
+            now: self.shape.zero(),
+            rest: self.shape.len(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub(self) fn sub_view(&self, dimension: usize, range: Range<usize>) -> Self {
+        let (shape, base, axes) =
+            split_layout(&self.shape, self.base, &self.axes, dimension, range);
+        // sub views on disjoint ranges of one dimension never share a cell
+        unsafe { Self::from_raw(self.list, shape, base, axes) }
+    }
+
+    pub fn split_at_mut(
+        self,
+        dimension: usize,
+        index: usize,
+    ) -> Result<(Self, Self), IndexCalculationError> {
+        let len = check_split(&self.shape, dimension, index)?;
+        Ok((
+            self.sub_view(dimension, 0..index),
+            self.sub_view(dimension, index..len),
+        ))
+    }
+
+    pub fn chunks_mut(
+        self,
+        dimension: usize,
+        size: usize,
+    ) -> Result<Vec<Self>, IndexCalculationError> {
+        Ok(chunk_ranges(&self.shape, dimension, size)?
+            .into_iter()
+            .map(|range| self.sub_view(dimension, range))
+            .collect())
+    }
+
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for cell in self.cells_mut() {
+            *cell = Some(value.clone());
+        }
+    }
+}
+
+pub struct MultiArrayViewIteratorMut<'b, T: Sized, S: Shape> {
+    pub(self) list: *mut Option<T>,
+    pub(self) shape: &'b S,
+    pub(self) base: usize,
+    pub(self) axes: &'b [ViewAxis],
+    pub(self) now: S::VectorType,
+    pub(self) rest: usize,
+    pub(self) _marker: PhantomData<&'b mut Option<T>>,
+}
+
+impl<'b, T: Sized, S: Shape> Iterator for MultiArrayViewIteratorMut<'b, T, S> {
+    type Item = &'b mut Option<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest == 0 {
+            return None;
+        }
+        let index = locate(self.shape, self.base, self.axes, &self.now);
+        self.rest -= 1;
+        if self.rest != 0 {
+            self.shape.next_vector(&mut self.now);
+        }
+        // every vector of the view is visited once and maps to a distinct cell
+        unsafe { Some(&mut *self.list.add(index)) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.rest, Some(self.rest))
+    }
+}
+
+impl<'a, T: Sized, S: Shape> Index<usize> for MultiArrayViewMut<'a, T, S> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self[&self.shape.vector(index)]
+    }
+}
+
+impl<'a, T: Sized, S: Shape> IndexMut<usize> for MultiArrayViewMut<'a, T, S> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let vector = self.shape.vector(index);
+        &mut self[&vector]
+    }
+}
+
+impl<'a, T: Sized, S: Shape> Index<&S::VectorType> for MultiArrayViewMut<'a, T, S> {
+    type Output = T;
+
+    fn index(&self, vector: &S::VectorType) -> &Self::Output {
+        match flat_index_of(&self.shape, self.base, &self.axes, vector) {
+            Ok(index) => match self.cell(index) {
+                Some(value) => value,
+                None => {
+                    panic!(
+                        "Element with index {} in the multi-array is not initialized",
+                        index
+                    )
+                }
+            },
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+impl<'a, T: Sized, S: Shape> IndexMut<&S::VectorType> for MultiArrayViewMut<'a, T, S> {
+    fn index_mut(&mut self, vector: &S::VectorType) -> &mut Self::Output {
+        match flat_index_of(&self.shape, self.base, &self.axes, vector) {
+            Ok(index) => match self.cell_mut(index) {
+                Some(value) => value,
+                None => {
+                    panic!(
+                        "Element with index {} in the multi-array is not initialized",
+                        index
+                    )
+                }
+            },
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+impl<'a, T: fmt::Display, S: Shape> fmt::Display for MultiArrayView<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let logical = row_major(&self.shape);
+        display_array(f, self.shape.shape(), |index| {
+            self.list[locate(&self.shape, self.base, &self.axes, &logical.vector(index))].as_ref()
+        })
+    }
+}
+
+impl<'a, T: fmt::Debug, S: Shape> fmt::Debug for MultiArrayView<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let logical = row_major(&self.shape);
+        debug_array(f, "MultiArrayView", self.shape.shape(), |index| {
+            self.list[locate(&self.shape, self.base, &self.axes, &logical.vector(index))].as_ref()
+        })
+    }
+}
+
+impl<'a, T: fmt::Display, S: Shape> fmt::Display for MultiArrayViewMut<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let logical = row_major(&self.shape);
+        display_array(f, self.shape.shape(), |index| {
+            self.cell(locate(
+                &self.shape,
+                self.base,
+                &self.axes,
+                &logical.vector(index),
+            ))
+            .as_ref()
+        })
+    }
+}
+
+impl<'a, T: fmt::Debug, S: Shape> fmt::Debug for MultiArrayViewMut<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let logical = row_major(&self.shape);
+        debug_array(f, "MultiArrayViewMut", self.shape.shape(), |index| {
+            self.cell(locate(
+                &self.shape,
+                self.base,
+                &self.axes,
+                &logical.vector(index),
+            ))
+            .as_ref()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn split_keeps_logical_indexes() {
+        let shape = Shape2::new([3, 4]).with_lower_bounds([1, -2]);
+        let array = MultiArray2::<i32>::new_by(shape, |i| i as i32);
+        let (lhs, rhs) = array.split_at(0, 1).unwrap();
+        assert_eq!(lhs.shape().lower_bounds(), &[1, -2]);
+        assert_eq!(rhs.shape().lower_bounds(), &[2, -2]);
+        assert_eq!(rhs[&[3, -2]], 8);
+        let selection = array.select(vec![(0, DummyIndex::from(2..=3))]).unwrap();
+        assert_eq!(selection.shape().lower_bounds(), &[2, -2]);
+        assert_eq!(selection[&vec![3, 1]], 11);
+    }
+
+    #[test]
+    fn iter_skips_uninitialized_cells() {
+        let mut array = MultiArray2::<i32>::new(Shape2::new([2, 2]));
+        array.set(&[0, 1], 1).unwrap();
+        array.set(&[1, 1], 3).unwrap();
+        let view = array.view();
+        assert_eq!(view.iter().cloned().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(
+            view.cells().cloned().collect::<Vec<_>>(),
+            vec![None, Some(1), None, Some(3)]
+        );
+        let mut view = array.view_mut();
+        for value in view.iter_mut() {
+            *value *= 10;
+        }
+        assert_eq!(view.cells_mut().filter(|cell| cell.is_none()).count(), 2);
+        assert_eq!(array.iter().cloned().collect::<Vec<_>>(), vec![10, 30]);
+    }
+
+    #[test]
+    fn get_mut_and_fill() {
+        let mut array = MultiArray2::<i32>::new_with(Shape2::new([3, 4]), 0);
+        array
+            .get_mut(dummy!(1.., (0..4).step_by(2)))
+            .unwrap()
+            .fill(1);
+        {
+            let mut view = array.get_mut(dummy!(0, vec![3isize, 1])).unwrap();
+            assert_eq!(view.shape().shape(), &[2]);
+            *view.get_mut(&vec![0]).unwrap() = Some(7);
+            *view.get_mut(&vec![1]).unwrap() = None;
+            assert!(view.get_mut(&vec![2]).is_err());
+        }
+        assert_eq!(
+            array.iter().cloned().collect::<Vec<_>>(),
+            vec![0, 0, 7, 1, 0, 1, 0, 1, 0, 1, 0]
+        );
+        assert!(!array.is_initialized(&[0, 1]));
+        assert!(array.get_mut(dummy!(3, ..)).is_err());
+    }
+
+    fn cells_of<S: Shape>(view: &mut MultiArrayViewMut<'_, i32, S>) -> Vec<usize> {
+        view.cells_mut()
+            .map(|cell| cell as *mut Option<i32> as usize)
+            .collect()
+    }
+
+    fn assert_disjoint(whole: Vec<usize>, parts: Vec<Vec<usize>>) {
+        let mut cells: Vec<usize> = parts.into_iter().flatten().collect();
+        let len = cells.len();
+        cells.sort();
+        cells.dedup();
+        assert_eq!(cells.len(), len);
+        let mut whole = whole;
+        whole.sort();
+        assert_eq!(cells, whole);
+    }
+
+    fn assert_split_disjoint<S: Shape>(
+        array: &mut MultiArray3<i32>,
+        view_of: for<'a> fn(&'a mut MultiArray3<i32>) -> MultiArrayViewMut<'a, i32, S>,
+    ) {
+        let whole = cells_of(&mut view_of(array));
+        let shape = view_of(array).shape().clone();
+        for dimension in 0..shape.dimension() {
+            let len = shape.shape()[dimension];
+            for index in 0..=len {
+                let (mut lhs, mut rhs) = view_of(array).split_at_mut(dimension, index).unwrap();
+                assert_disjoint(whole.clone(), vec![cells_of(&mut lhs), cells_of(&mut rhs)]);
+            }
+            for size in 1..=len {
+                let chunks = view_of(array).chunks_mut(dimension, size).unwrap();
+                assert_disjoint(
+                    whole.clone(),
+                    chunks
+                        .into_iter()
+                        .map(|mut chunk| cells_of(&mut chunk))
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn split_mut_is_disjoint() {
+        let mut array = MultiArray3::<i32>::new_by(Shape3::new([3, 5, 2]), |i| i as i32);
+        assert_split_disjoint(&mut array, |array| array.view_mut());
+        assert_split_disjoint(&mut array, |array| {
+            array.get_mut(dummy!(.., (0..5).step_by(2), ..)).unwrap()
+        });
+        assert_split_disjoint(&mut array, |array| {
+            array.get_mut(dummy!(.., (..).step_by(-3), 1)).unwrap()
+        });
+        assert_split_disjoint(&mut array, |array| {
+            array
+                .get_mut(dummy!(vec![2isize, 0], vec![4isize, 1, 3], ..))
+                .unwrap()
+        });
+    }
+
+    #[test]
+    fn split_mut_writes_stay_apart() {
+        let mut array = MultiArray2::<i32>::new_by(Shape2::new([4, 6]), |_| 0);
+        {
+            let (top, bottom) = array.split_at_mut(0, 2).unwrap();
+            let (mut left, mut right) = top.split_at_mut(1, 3).unwrap();
+            let mut chunks = bottom.chunks_mut(1, 4).unwrap();
+            let (mut first, mut second) = (chunks.remove(0), chunks.remove(0));
+            for (k, (lhs, rhs)) in left.cells_mut().zip(right.cells_mut()).enumerate() {
+                *lhs = Some(1 + k as i32);
+                *rhs = Some(10 + k as i32);
+            }
+            first.fill(100);
+            second.fill(200);
+        }
+        assert_eq!(
+            array.iter().cloned().collect::<Vec<_>>(),
+            vec![
+                1, 2, 3, 10, 11, 12, 4, 5, 6, 13, 14, 15, 100, 100, 100, 100, 200, 200, 100, 100,
+                100, 100, 200, 200
+            ]
+        );
+
+        let mut array = MultiArray2::<i32>::new_by(Shape2::new([2, 6]), |_| 0);
+        {
+            let view = array.get_mut(dummy!(.., (0..6).step_by(2))).unwrap();
+            let parts = view.chunks_mut(1, 1).unwrap();
+            std::thread::scope(|scope| {
+                for (k, mut part) in parts.into_iter().enumerate() {
+                    scope.spawn(move || part.fill(k as i32 + 1));
+                }
+            });
+        }
+        assert_eq!(
+            array.iter().cloned().collect::<Vec<_>>(),
+            vec![1, 0, 2, 0, 3, 0, 1, 0, 2, 0, 3, 0]
+        );
+    }
 }
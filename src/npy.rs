@@ -0,0 +1,658 @@
+use crate::shape::row_major;
+use crate::*;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+const ZIP_LOCAL_HEADER: u32 = 0x04034b50;
+const ZIP_CENTRAL_HEADER: u32 = 0x02014b50;
+const ZIP_END_OF_CENTRAL_DIRECTORY: u32 = 0x06054b50;
+
+pub enum NpyError {
+    Io(io::Error),
+    InvalidFormat(String),
+    TypeMismatching { descr: String, expected: String },
+    DimensionMismatching(DimensionMismatchingError),
+    Uninitialized(UninitializedError),
+    NotFound(String),
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NpyError::Io(err) => {
+                write!(f, "{}", err)
+            }
+            NpyError::InvalidFormat(message) => {
+                write!(f, "Invalid npy format: {}.", message)
+            }
+            NpyError::TypeMismatching { descr, expected } => {
+                write!(f, "Dtype should be {}, not {}.", expected, descr)
+            }
+            NpyError::DimensionMismatching(err) => {
+                write!(f, "{}", err)
+            }
+            NpyError::Uninitialized(err) => {
+                write!(f, "{}", err)
+            }
+            NpyError::NotFound(name) => {
+                write!(f, "Array {} is not found in the npz file.", name)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl From<io::Error> for NpyError {
+    fn from(err: io::Error) -> Self {
+        NpyError::Io(err)
+    }
+}
+
+pub trait NpyElement: Sized + Copy {
+    const KIND: &'static str;
+    const SIZE: usize;
+
+    fn write_to(&self, buffer: &mut Vec<u8>);
+    fn read_from(bytes: &[u8], little_endian: bool) -> Self;
+
+    fn descr() -> String {
+        if Self::SIZE == 1 {
+            format!("|{}{}", Self::KIND, Self::SIZE)
+        } else {
+            format!("<{}{}", Self::KIND, Self::SIZE)
+        }
+    }
+}
+
+macro_rules! npy_element {
+    ($type:ty, $kind:literal, $size:literal) => {
+        impl NpyElement for $type {
+            const KIND: &'static str = $kind;
+            const SIZE: usize = $size;
+
+            fn write_to(&self, buffer: &mut Vec<u8>) {
+                buffer.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_from(bytes: &[u8], little_endian: bool) -> Self {
+                let mut value = [0; $size];
+                value.copy_from_slice(bytes);
+                if little_endian {
+                    <$type>::from_le_bytes(value)
+                } else {
+                    <$type>::from_be_bytes(value)
+                }
+            }
+        }
+    };
+}
+
+npy_element!(i8, "i", 1);
+npy_element!(i16, "i", 2);
+npy_element!(i32, "i", 4);
+npy_element!(i64, "i", 8);
+npy_element!(u8, "u", 1);
+npy_element!(u16, "u", 2);
+npy_element!(u32, "u", 4);
+npy_element!(u64, "u", 8);
+npy_element!(f32, "f", 4);
+npy_element!(f64, "f", 8);
+
+impl NpyElement for bool {
+    const KIND: &'static str = "b";
+    const SIZE: usize = 1;
+
+    fn write_to(&self, buffer: &mut Vec<u8>) {
+        buffer.push(*self as u8);
+    }
+
+    fn read_from(bytes: &[u8], _: bool) -> Self {
+        bytes[0] != 0
+    }
+}
+
+fn header_of(descr: &str, fortran_order: bool, shape: &[usize]) -> Vec<u8> {
+    let shape = match shape.len() {
+        1 => format!("({},)", shape[0]),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(|len| len.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+        descr,
+        if fortran_order { "True" } else { "False" },
+        shape
+    );
+    let len = NPY_MAGIC.len() + 4 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - len % 64) % 64));
+    header.push('\n');
+
+    let mut ret = Vec::with_capacity(NPY_MAGIC.len() + 4 + header.len());
+    ret.extend_from_slice(NPY_MAGIC);
+    ret.extend_from_slice(&[1, 0]);
+    ret.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    ret.extend_from_slice(header.as_bytes());
+    ret
+}
+
+fn npy_bytes_of<T: NpyElement, S: Shape, St: Storage<T>>(
+    array: &MultiArray<T, S, St>,
+) -> Result<Vec<u8>, NpyError> {
+    let fortran_order = array.shape.dimension() > 1 && array.shape.layout() == Layout::ColumnMajor;
+    let logical = if fortran_order {
+        array.shape.clone()
+    } else {
+        row_major(&array.shape)
+    };
+    let mut buffer = header_of(&T::descr(), fortran_order, array.shape.shape());
+    buffer.reserve(array.shape.len() * T::SIZE);
+    for index in 0..array.shape.len() {
+        let index = array.shape.index(&logical.vector(index)).unwrap();
+        match array.list.cell(index) {
+            Some(value) => value.write_to(&mut buffer),
+            None => {
+                return Err(NpyError::Uninitialized(UninitializedError { index }));
+            }
+        }
+    }
+    Ok(buffer)
+}
+
+pub fn write_npy<W: Write, T: NpyElement, S: Shape, St: Storage<T>>(
+    writer: &mut W,
+    array: &MultiArray<T, S, St>,
+) -> Result<(), NpyError> {
+    writer.write_all(&npy_bytes_of(array)?)?;
+    Ok(())
+}
+
+pub fn save_npy<P: AsRef<Path>, T: NpyElement, S: Shape, St: Storage<T>>(
+    path: P,
+    array: &MultiArray<T, S, St>,
+) -> Result<(), NpyError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_npy(&mut writer, array)?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn value_of<'a>(header: &'a str, key: &str) -> Result<&'a str, NpyError> {
+    let pattern = format!("'{}':", key);
+    match header.find(&pattern) {
+        Some(position) => Ok(header[(position + pattern.len())..].trim_start()),
+        None => Err(NpyError::InvalidFormat(format!("missing key {}", key))),
+    }
+}
+
+fn parse_header(header: &str) -> Result<(String, bool, Vec<usize>), NpyError> {
+    let descr = value_of(header, "descr")?;
+    let descr = match descr
+        .strip_prefix('\'')
+        .and_then(|rest| rest.split('\'').next())
+    {
+        Some(descr) => descr.to_string(),
+        None => return Err(NpyError::InvalidFormat(String::from("invalid descr"))),
+    };
+
+    let fortran_order = value_of(header, "fortran_order")?;
+    let fortran_order = if fortran_order.starts_with("True") {
+        true
+    } else if fortran_order.starts_with("False") {
+        false
+    } else {
+        return Err(NpyError::InvalidFormat(String::from(
+            "invalid fortran_order",
+        )));
+    };
+
+    let shape = value_of(header, "shape")?;
+    let shape = match shape
+        .strip_prefix('(')
+        .and_then(|rest| rest.split(')').next())
+    {
+        Some(shape) => shape,
+        None => return Err(NpyError::InvalidFormat(String::from("invalid shape"))),
+    };
+    let mut lens = Vec::new();
+    for len in shape
+        .split(',')
+        .map(|len| len.trim())
+        .filter(|len| !len.is_empty())
+    {
+        match len.trim_end_matches('L').parse::<usize>() {
+            Ok(len) => lens.push(len),
+            Err(_) => return Err(NpyError::InvalidFormat(String::from("invalid shape"))),
+        }
+    }
+    Ok((descr, fortran_order, lens))
+}
+
+pub fn read_npy<R: Read, T: NpyElement>(reader: &mut R) -> Result<DynMultiArray<T>, NpyError> {
+    let mut magic = [0; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic[0..6] != NPY_MAGIC {
+        return Err(NpyError::InvalidFormat(String::from(
+            "invalid magic string",
+        )));
+    }
+    let header_len = match magic[6] {
+        1 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        version => {
+            return Err(NpyError::InvalidFormat(format!(
+                "unsupported version {}",
+                version
+            )))
+        }
+    };
+    let mut header = vec![0; header_len];
+    reader.read_exact(&mut header)?;
+    let header = match String::from_utf8(header) {
+        Ok(header) => header,
+        Err(_) => return Err(NpyError::InvalidFormat(String::from("invalid header"))),
+    };
+    let (descr, fortran_order, lens) = parse_header(&header)?;
+
+    let little_endian = match descr.chars().next() {
+        Some('<') | Some('|') => true,
+        Some('>') => false,
+        Some('=') => cfg!(target_endian = "little"),
+        _ => {
+            return Err(NpyError::TypeMismatching {
+                descr,
+                expected: T::descr(),
+            })
+        }
+    };
+    if descr[1..] != T::descr()[1..] {
+        return Err(NpyError::TypeMismatching {
+            descr,
+            expected: T::descr(),
+        });
+    }
+
+    let size = match lens
+        .iter()
+        .try_fold(T::SIZE, |size, len| size.checked_mul(*len))
+    {
+        Some(size) => size,
+        None => return Err(NpyError::InvalidFormat(String::from("shape too large"))),
+    };
+    let mut shape = DynShape::from_slice(&lens).map_err(NpyError::DimensionMismatching)?;
+    if fortran_order {
+        shape.set_layout(Layout::ColumnMajor).unwrap();
+    }
+    let mut data = Vec::new();
+    reader.take(size as u64).read_to_end(&mut data)?;
+    if data.len() != size {
+        return Err(NpyError::InvalidFormat(String::from("truncated data")));
+    }
+    let values: Vec<T> = data
+        .chunks_exact(T::SIZE)
+        .map(|bytes| T::read_from(bytes, little_endian))
+        .collect();
+
+    let list = values.into_iter().map(Option::Some).collect();
+    Ok(MultiArray::from_storage(shape, list).unwrap())
+}
+
+pub fn read_npy_as<R: Read, T: NpyElement, S: Shape>(
+    reader: &mut R,
+) -> Result<MultiArray<T, S>, NpyError> {
+    let array = read_npy(reader)?;
+    let shape = S::from_slice(array.shape.shape())
+        .and_then(|shape| shape.with_layout(array.shape.layout()))
+        .map_err(NpyError::DimensionMismatching)?;
+    Ok(array.reshape(shape).unwrap())
+}
+
+pub fn load_npy<P: AsRef<Path>, T: NpyElement>(path: P) -> Result<DynMultiArray<T>, NpyError> {
+    read_npy(&mut BufReader::new(File::open(path)?))
+}
+
+pub fn load_npy_as<P: AsRef<Path>, T: NpyElement, S: Shape>(
+    path: P,
+) -> Result<MultiArray<T, S>, NpyError> {
+    read_npy_as(&mut BufReader::new(File::open(path)?))
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffffffffu32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn u16_at(bytes: &[u8], position: usize) -> Result<u16, NpyError> {
+    match bytes.get(position..(position + 2)) {
+        Some(value) => Ok(u16::from_le_bytes([value[0], value[1]])),
+        None => Err(NpyError::InvalidFormat(String::from("truncated npz file"))),
+    }
+}
+
+fn u32_at(bytes: &[u8], position: usize) -> Result<u32, NpyError> {
+    match bytes.get(position..(position + 4)) {
+        Some(value) => Ok(u32::from_le_bytes([value[0], value[1], value[2], value[3]])),
+        None => Err(NpyError::InvalidFormat(String::from("truncated npz file"))),
+    }
+}
+
+fn zip_u32(value: usize) -> Result<u32, NpyError> {
+    if value > u32::MAX as usize {
+        Err(NpyError::InvalidFormat(String::from(
+            "npz files larger than 4 GiB are not supported",
+        )))
+    } else {
+        Ok(value as u32)
+    }
+}
+
+pub struct NpzWriter<W: Write> {
+    pub(self) writer: W,
+    pub(self) position: usize,
+    pub(self) directory: Vec<u8>,
+    pub(self) len: usize,
+}
+
+impl<W: Write> NpzWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            position: 0,
+            directory: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn add_array<T: NpyElement, S: Shape, St: Storage<T>>(
+        &mut self,
+        name: &str,
+        array: &MultiArray<T, S, St>,
+    ) -> Result<(), NpyError> {
+        let data = npy_bytes_of(array)?;
+        let name = format!("{}.npy", name);
+        let crc = crc32(&data);
+        let size = zip_u32(data.len())?;
+        let offset = zip_u32(self.position)?;
+
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend_from_slice(&ZIP_LOCAL_HEADER.to_le_bytes());
+        header.extend_from_slice(&20u16.to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(&0x21u16.to_le_bytes());
+        header.extend_from_slice(&crc.to_le_bytes());
+        header.extend_from_slice(&size.to_le_bytes());
+        header.extend_from_slice(&size.to_le_bytes());
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(name.as_bytes());
+        self.writer.write_all(&header)?;
+        self.writer.write_all(&data)?;
+        self.position += header.len() + data.len();
+
+        self.directory
+            .extend_from_slice(&ZIP_CENTRAL_HEADER.to_le_bytes());
+        self.directory.extend_from_slice(&20u16.to_le_bytes());
+        self.directory.extend_from_slice(&header[4..30]);
+        self.directory.extend_from_slice(&0u16.to_le_bytes());
+        self.directory.extend_from_slice(&0u16.to_le_bytes());
+        self.directory.extend_from_slice(&0u16.to_le_bytes());
+        self.directory.extend_from_slice(&0u32.to_le_bytes());
+        self.directory.extend_from_slice(&offset.to_le_bytes());
+        self.directory.extend_from_slice(name.as_bytes());
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W, NpyError> {
+        let mut end = Vec::with_capacity(22);
+        end.extend_from_slice(&ZIP_END_OF_CENTRAL_DIRECTORY.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes());
+        end.extend_from_slice(&(self.len as u16).to_le_bytes());
+        end.extend_from_slice(&(self.len as u16).to_le_bytes());
+        end.extend_from_slice(&zip_u32(self.directory.len())?.to_le_bytes());
+        end.extend_from_slice(&zip_u32(self.position)?.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes());
+        self.writer.write_all(&self.directory)?;
+        self.writer.write_all(&end)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+pub struct NpzReader {
+    pub(self) bytes: Vec<u8>,
+    pub(self) entries: Vec<(String, usize, usize)>,
+}
+
+impl NpzReader {
+    pub fn new<R: Read>(reader: &mut R) -> Result<Self, NpyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let lower_bound = bytes.len().saturating_sub(22 + u16::MAX as usize);
+        let mut end = None;
+        for position in (lower_bound..=bytes.len().saturating_sub(22)).rev() {
+            if u32_at(&bytes, position)? == ZIP_END_OF_CENTRAL_DIRECTORY {
+                end = Some(position);
+                break;
+            }
+        }
+        let end = match end {
+            Some(end) => end,
+            None => {
+                return Err(NpyError::InvalidFormat(String::from(
+                    "missing end of central directory",
+                )))
+            }
+        };
+
+        let len = u16_at(&bytes, end + 10)? as usize;
+        let mut position = u32_at(&bytes, end + 16)? as usize;
+        let mut entries = Vec::with_capacity(len);
+        for _ in 0..len {
+            if u32_at(&bytes, position)? != ZIP_CENTRAL_HEADER {
+                return Err(NpyError::InvalidFormat(String::from(
+                    "invalid central directory",
+                )));
+            }
+            if u16_at(&bytes, position + 10)? != 0 {
+                return Err(NpyError::InvalidFormat(String::from(
+                    "compressed npz entries are not supported",
+                )));
+            }
+            let size = u32_at(&bytes, position + 20)? as usize;
+            let name_len = u16_at(&bytes, position + 28)? as usize;
+            let extra_len = u16_at(&bytes, position + 30)? as usize;
+            let comment_len = u16_at(&bytes, position + 32)? as usize;
+            let offset = u32_at(&bytes, position + 42)? as usize;
+            let name = match bytes.get((position + 46)..(position + 46 + name_len)) {
+                Some(name) => String::from_utf8_lossy(name).into_owned(),
+                None => return Err(NpyError::InvalidFormat(String::from("truncated npz file"))),
+            };
+            if u32_at(&bytes, offset)? != ZIP_LOCAL_HEADER {
+                return Err(NpyError::InvalidFormat(String::from(
+                    "invalid local header",
+                )));
+            }
+            let start = offset
+                + 30
+                + u16_at(&bytes, offset + 26)? as usize
+                + u16_at(&bytes, offset + 28)? as usize;
+            if start + size > bytes.len() {
+                return Err(NpyError::InvalidFormat(String::from("truncated npz file")));
+            }
+            let name = match name.strip_suffix(".npy") {
+                Some(name) => name.to_string(),
+                None => name,
+            };
+            entries.push((name, start, size));
+            position += 46 + name_len + extra_len + comment_len;
+        }
+        Ok(Self { bytes, entries })
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, NpyError> {
+        Self::new(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .map(|(name, _, _)| name.as_str())
+            .collect()
+    }
+
+    pub(self) fn data_of(&self, name: &str) -> Result<&[u8], NpyError> {
+        match self.entries.iter().find(|(entry, _, _)| entry == name) {
+            Some((_, start, size)) => Ok(&self.bytes[*start..(*start + *size)]),
+            None => Err(NpyError::NotFound(name.to_string())),
+        }
+    }
+
+    pub fn array<T: NpyElement>(&self, name: &str) -> Result<DynMultiArray<T>, NpyError> {
+        read_npy(&mut self.data_of(name)?)
+    }
+
+    pub fn array_as<T: NpyElement, S: Shape>(
+        &self,
+        name: &str,
+    ) -> Result<MultiArray<T, S>, NpyError> {
+        read_npy_as(&mut self.data_of(name)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn npy_of(header: &str, data: &[u8]) -> Vec<u8> {
+        let mut bytes = NPY_MAGIC.to_vec();
+        bytes.extend_from_slice(&[1, 0]);
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    fn values_of<T: Clone, S: Shape>(array: &MultiArray<T, S>) -> Vec<T> {
+        array
+            .to_layout(Layout::RowMajor)
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn round_trip_c_order() {
+        let array = MultiArray::new_by(Shape2::new([2, 3]), |i| i as f64 * 0.5);
+        let mut bytes = Vec::new();
+        write_npy(&mut bytes, &array).unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains("'fortran_order': False"));
+        let read: MultiArray<f64, Shape2> = read_npy_as(&mut bytes.as_slice()).unwrap();
+        assert_eq!(read.shape().layout(), Layout::RowMajor);
+        assert_eq!(values_of(&read), values_of(&array));
+    }
+
+    #[test]
+    fn round_trip_fortran_order() {
+        let array = MultiArray::new_by(Shape3::new([2, 3, 4]), |i| i as i32)
+            .to_layout(Layout::ColumnMajor)
+            .unwrap();
+        let mut bytes = Vec::new();
+        write_npy(&mut bytes, &array).unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains("'fortran_order': True"));
+        let read = read_npy::<_, i32>(&mut bytes.as_slice()).unwrap();
+        assert_eq!(read.shape().layout(), Layout::ColumnMajor);
+        assert_eq!(read[&vec![1, 2, 3]], array[&[1, 2, 3]]);
+        assert_eq!(values_of(&read), values_of(&array));
+    }
+
+    #[test]
+    fn round_trip_custom_order() {
+        let array = MultiArray::new_by(Shape3::new([2, 3, 4]), |i| i as u16)
+            .to_layout(Layout::Order(vec![1, 0, 2]))
+            .unwrap();
+        let mut bytes = Vec::new();
+        write_npy(&mut bytes, &array).unwrap();
+        let read = read_npy::<_, u16>(&mut bytes.as_slice()).unwrap();
+        assert_eq!(values_of(&read), values_of(&array));
+    }
+
+    #[test]
+    fn hand_made_header() {
+        let header = "{'descr': '>i2', 'fortran_order': True, 'shape': (2, 3), }\n";
+        let mut data = Vec::new();
+        for value in [0i16, 3, 1, 4, 2, 5] {
+            data.extend_from_slice(&value.to_be_bytes());
+        }
+        let read = read_npy::<_, i16>(&mut npy_of(header, &data).as_slice()).unwrap();
+        assert_eq!(read.shape().shape(), &[2, 3]);
+        assert_eq!(values_of(&read), vec![0, 1, 2, 3, 4, 5]);
+        assert!(read_npy::<_, i32>(&mut npy_of(header, &data).as_slice()).is_err());
+        assert!(read_npy::<_, i16>(&mut npy_of(header, &data[..10]).as_slice()).is_err());
+    }
+
+    #[test]
+    fn oversized_shape() {
+        let header =
+            "{'descr': '<i8', 'fortran_order': False, 'shape': (18446744073709551615, 2), }\n";
+        match read_npy::<_, i64>(&mut npy_of(header, &[]).as_slice()) {
+            Err(NpyError::InvalidFormat(_)) => {}
+            _ => panic!("an oversized shape should be rejected"),
+        }
+    }
+
+    #[test]
+    fn round_trip_npz() {
+        let lhs = MultiArray::new_by(Shape2::new([2, 2]), |i| i as f32);
+        let rhs = MultiArray::new_by(Shape1::new([3]), |i| i % 2 == 0)
+            .to_layout(Layout::ColumnMajor)
+            .unwrap();
+        let mut writer = NpzWriter::new(Vec::new());
+        writer.add_array("lhs", &lhs).unwrap();
+        writer.add_array("rhs", &rhs).unwrap();
+        let bytes = writer.finish().unwrap();
+        let reader = NpzReader::new(&mut bytes.as_slice()).unwrap();
+        assert_eq!(reader.names(), vec!["lhs", "rhs"]);
+        let read: MultiArray<f32, Shape2> = reader.array_as("lhs").unwrap();
+        assert_eq!(values_of(&read), values_of(&lhs));
+        let read = reader.array::<bool>("rhs").unwrap();
+        assert_eq!(values_of(&read), vec![true, false, true]);
+        assert!(reader.array::<bool>("missing").is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use crate::shape::{row_major, with_lower_bounds, with_names};
+use crate::*;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::ops::Bound;
+
+// shapes starting from zero without names keep the plain list of lengths
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum ShapeData {
+    Lens(Vec<usize>),
+    Labeled {
+        shape: Vec<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        lower_bounds: Option<Vec<isize>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        names: Option<Vec<Option<Cow<'static, str>>>>,
+    },
+}
+
+fn serialize_shape<S: Shape, Se: Serializer>(
+    shape: &S,
+    serializer: Se,
+) -> Result<Se::Ok, Se::Error> {
+    let lower_bounds = if shape
+        .lower_bounds()
+        .iter()
+        .all(|lower_bound| *lower_bound == 0)
+    {
+        None
+    } else {
+        Some(shape.lower_bounds().to_vec())
+    };
+    let names = if shape.names().iter().all(Option::is_none) {
+        None
+    } else {
+        Some(shape.names().to_vec())
+    };
+    if lower_bounds.is_none() && names.is_none() {
+        shape.shape().serialize(serializer)
+    } else {
+        ShapeData::Labeled {
+            shape: shape.shape().to_vec(),
+            lower_bounds,
+            names,
+        }
+        .serialize(serializer)
+    }
+}
+
+fn deserialize_shape<'de, S: Shape, D: Deserializer<'de>>(deserializer: D) -> Result<S, D::Error> {
+    match ShapeData::deserialize(deserializer)? {
+        ShapeData::Lens(shape) => S::from_slice(&shape).map_err(de::Error::custom),
+        ShapeData::Labeled {
+            shape,
+            lower_bounds,
+            names,
+        } => {
+            let lens = [
+                lower_bounds.as_ref().map(Vec::len),
+                names.as_ref().map(Vec::len),
+            ];
+            if let Some(len) = lens.into_iter().flatten().find(|len| *len != shape.len()) {
+                return Err(de::Error::custom(DimensionMismatchingError {
+                    dimension: shape.len(),
+                    vector_dimension: len,
+                }));
+            }
+            let mut shape = S::from_slice(&shape).map_err(de::Error::custom)?;
+            if let Some(lower_bounds) = lower_bounds {
+                shape = with_lower_bounds(shape, &lower_bounds);
+            }
+            if let Some(names) = names {
+                shape = with_names(shape, &names);
+            }
+            Ok(shape)
+        }
+    }
+}
+
+impl<const N: usize> Serialize for StaticShape<N> {
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        serialize_shape(self, serializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for StaticShape<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_shape(deserializer)
+    }
+}
+
+impl Serialize for DynShape {
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        serialize_shape(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DynShape {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_shape(deserializer)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "DummyIndex")]
+enum DummyIndexData {
+    Index(isize),
+    Range {
+        start: Bound<isize>,
+        end: Bound<isize>,
+    },
+    Strided {
+        start: Bound<isize>,
+        end: Bound<isize>,
+        step: isize,
+    },
+    IndexArray(Vec<isize>),
+}
+
+impl Serialize for DummyIndex {
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        match self {
+            DummyIndex::Index(index) => DummyIndexData::Index(*index),
+            DummyIndex::Range(range) => DummyIndexData::Range {
+                start: range.start_bound(),
+                end: range.end_bound(),
+            },
+            DummyIndex::Strided { range, step } => DummyIndexData::Strided {
+                start: range.start_bound(),
+                end: range.end_bound(),
+                step: *step,
+            },
+            DummyIndex::IndexArray(indexes) => DummyIndexData::IndexArray(indexes.clone()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DummyIndex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match DummyIndexData::deserialize(deserializer)? {
+            DummyIndexData::Index(index) => DummyIndex::Index(index),
+            DummyIndexData::Range { start, end } => DummyIndex::Range(Box::new((start, end))),
+            DummyIndexData::Strided { start, end, step } => DummyIndex::Strided {
+                range: Box::new((start, end)),
+                step,
+            },
+            DummyIndexData::IndexArray(indexes) => DummyIndex::IndexArray(indexes),
+        })
+    }
+}
+
+struct CellsData<'a, T: Sized, S: Shape, St: Storage<T>> {
+    pub(self) array: &'a MultiArray<T, S, St>,
+}
+
+impl<'a, T: Sized + Serialize, S: Shape, St: Storage<T>> Serialize for CellsData<'a, T, S, St> {
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        let shape = &self.array.shape;
+        let logical = row_major(shape);
+        serializer.collect_seq((0..shape.len()).map(|index| {
+            self.array
+                .list
+                .cell(shape.index(&logical.vector(index)).unwrap())
+        }))
+    }
+}
+
+impl<T: Sized + Serialize, S: Shape + Serialize, St: Storage<T>> Serialize
+    for MultiArray<T, S, St>
+{
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        let mut state = serializer.serialize_struct("MultiArray", 2)?;
+        state.serialize_field("shape", &self.shape)?;
+        state.serialize_field("data", &CellsData { array: self })?;
+        state.end()
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "MultiArray")]
+struct MultiArrayData<T, S> {
+    shape: S,
+    data: Vec<Option<T>>,
+}
+
+impl<'de, T, S, St> Deserialize<'de> for MultiArray<T, S, St>
+where
+    T: Sized + Deserialize<'de>,
+    S: Shape + Deserialize<'de>,
+    St: PartialStorage<T>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = MultiArrayData::<T, S>::deserialize(deserializer)?;
+        if data.data.len() != data.shape.len() {
+            return Err(de::Error::custom(LengthMismatchingError {
+                len: data.data.len(),
+                shape_len: data.shape.len(),
+            }));
+        }
+        let mut list = St::empty(data.shape.len());
+        for (index, value) in data.data.into_iter().enumerate() {
+            if let Some(value) = value {
+                list.set_cell(index, value);
+            }
+        }
+        Ok(MultiArray {
+            list,
+            shape: data.shape,
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn lower_bounds_round_trip() {
+        let shape = Shape2::new([2, 3]).with_lower_bounds([1, 0]);
+        let array = MultiArray2::<i32>::new_by(shape, |i| i as i32);
+        let json = serde_json::to_string(&array).unwrap();
+        assert_eq!(
+            json,
+            r#"{"shape":{"shape":[2,3],"lower_bounds":[1,0]},"data":[0,1,2,3,4,5]}"#
+        );
+        let other: MultiArray2<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(other.shape().lower_bounds(), &[1, 0]);
+        assert_eq!(other[&[2, 0]], 3);
+
+        let shape = DynShape::new(vec![2, 1])
+            .with_names(&[String::from("row"), String::from("column")])
+            .unwrap();
+        let json = serde_json::to_string(&shape).unwrap();
+        assert_eq!(json, r#"{"shape":[2,1],"names":["row","column"]}"#);
+        let other: DynShape = serde_json::from_str(&json).unwrap();
+        assert_eq!(other.name_of_dimension(1), Some("column"));
+        assert_eq!(other.lower_bounds(), &[0, 0]);
+
+        let json = serde_json::to_string(&MultiArray1::new_with(Shape1::new([2]), 0)).unwrap();
+        assert_eq!(json, r#"{"shape":[2],"data":[0,0]}"#);
+        assert!(serde_json::from_str::<DynMultiArray<i32>>(
+            r#"{"shape":{"shape":[2],"lower_bounds":[1,0]},"data":[0,0]}"#
+        )
+        .is_err());
+    }
+}
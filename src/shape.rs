@@ -1,7 +1,9 @@
 use super::dummy_vector::DummyIndex;
+use super::map_vector::MapIndex;
+use std::borrow::Cow;
 use std::fmt;
 use std::mem;
-use std::ops::IndexMut;
+use std::ops::{Index, IndexMut, Range};
 
 const DYN_DIMENSION: usize = usize::MAX;
 
@@ -31,37 +33,178 @@ impl fmt::Debug for DimensionMismatchingError {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct OutOfShapeError {
     pub dimension: usize,
+    pub name: Option<Cow<'static, str>>,
+    pub lower_bound: isize,
     pub len: usize,
     pub vector_index: isize,
 }
 
 impl fmt::Display for OutOfShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Length of dimension {}", self.dimension)?;
+        if let Some(name) = &self.name {
+            write!(f, " ({})", name)?;
+        }
+        if self.lower_bound == 0 {
+            write!(f, " is {}", self.len)?;
+        } else {
+            write!(
+                f,
+                " is {} ({}..{})",
+                self.len,
+                self.lower_bound,
+                self.lower_bound + self.len as isize
+            )?;
+        }
+        write!(f, ", but it get {}.", self.vector_index)
+    }
+}
+
+impl fmt::Debug for OutOfShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+#[derive(Clone)]
+pub struct UnknownDimensionError {
+    pub name: Cow<'static, str>,
+}
+
+impl fmt::Display for UnknownDimensionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "There is no dimension named {}.", self.name)
+    }
+}
+
+impl fmt::Debug for UnknownDimensionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "There is no dimension named {}.", self.name)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct InvalidStepError {
+    pub dimension: usize,
+    pub step: isize,
+}
+
+impl fmt::Display for InvalidStepError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Length of dimension {} is {}, but it get {}.",
-            self.dimension, self.len, self.vector_index
+            "Step of dimension {} should not be {}.",
+            self.dimension, self.step
         )
     }
 }
 
-impl fmt::Debug for OutOfShapeError {
+impl fmt::Debug for InvalidStepError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ShapeMismatchingError {
+    pub dimension: usize,
+    pub len: usize,
+    pub broadcast_len: usize,
+}
+
+impl fmt::Display for ShapeMismatchingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Dimension {} of length {} cannot be broadcast to length {}.",
+            self.dimension, self.len, self.broadcast_len
+        )
+    }
+}
+
+impl fmt::Debug for ShapeMismatchingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct LengthMismatchingError {
+    pub len: usize,
+    pub shape_len: usize,
+}
+
+impl fmt::Display for LengthMismatchingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Length of dimension {} is {}, but it get {}.",
-            self.dimension, self.len, self.vector_index
+            "Length of shape should be {}, not {}.",
+            self.len, self.shape_len
         )
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+impl fmt::Debug for LengthMismatchingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Length of shape should be {}, not {}.",
+            self.len, self.shape_len
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct LayoutMismatchingError {
+    pub layout: Layout,
+}
+
+impl fmt::Display for LayoutMismatchingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Layout should be {:?}, not {:?}.",
+            Layout::RowMajor,
+            self.layout
+        )
+    }
+}
+
+impl fmt::Debug for LayoutMismatchingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ReshapeError {
+    LengthMismatching(LengthMismatchingError),
+    LayoutMismatching(LayoutMismatchingError),
+}
+
+impl fmt::Display for ReshapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReshapeError::LengthMismatching(err) => {
+                write!(f, "{}", err)
+            }
+            ReshapeError::LayoutMismatching(err) => {
+                write!(f, "{}", err)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum IndexCalculationError {
     DimensionMismatching(DimensionMismatchingError),
     OutOfShape(OutOfShapeError),
+    UnknownDimension(UnknownDimensionError),
+    InvalidStep(InvalidStepError),
+    ShapeMismatching(ShapeMismatchingError),
 }
 
 impl fmt::Display for IndexCalculationError {
@@ -73,18 +216,65 @@ impl fmt::Display for IndexCalculationError {
             IndexCalculationError::OutOfShape(err) => {
                 write!(f, "{}", err)
             }
+            IndexCalculationError::UnknownDimension(err) => {
+                write!(f, "{}", err)
+            }
+            IndexCalculationError::InvalidStep(err) => {
+                write!(f, "{}", err)
+            }
+            IndexCalculationError::ShapeMismatching(err) => {
+                write!(f, "{}", err)
+            }
         }
     }
 }
 
-pub trait Shape {
+#[derive(Clone, Copy)]
+pub struct UninitializedError {
+    pub index: usize,
+}
+
+impl fmt::Display for UninitializedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Element with index {} in the multi-array is not initialized.",
+            self.index
+        )
+    }
+}
+
+impl fmt::Debug for UninitializedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Element with index {} in the multi-array is not initialized.",
+            self.index
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layout {
+    RowMajor,
+    ColumnMajor,
+    Order(Vec<usize>),
+}
+
+pub trait Shape: Clone {
     const DIMENSION: usize;
-    type VectorType: IndexMut<usize, Output = usize>;
+    type VectorType: IndexMut<usize, Output = isize> + Clone;
     type DummyVectorType: IndexMut<usize, Output = DummyIndex>;
+    type MapVectorType<'a>: Index<usize, Output = MapIndex<'a>>;
+
+    fn from_slice(shape: &[usize]) -> Result<Self, DimensionMismatchingError>;
 
     fn zero(&self) -> Self::VectorType;
 
     fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
     fn dimension(&self) -> usize {
         Self::DIMENSION
     }
@@ -95,10 +285,83 @@ pub trait Shape {
     fn shape(&self) -> &[usize];
     fn offset(&self) -> &[usize];
 
+    // dimensions from the outermost one to the innermost one in the storage
+    fn order(&self) -> &[usize];
+    fn set_layout(&mut self, layout: Layout) -> Result<(), DimensionMismatchingError>;
+
+    fn layout(&self) -> Layout {
+        let order = self.order();
+        if order
+            .iter()
+            .enumerate()
+            .all(|(i, dimension)| i == *dimension)
+        {
+            Layout::RowMajor
+        } else if order
+            .iter()
+            .rev()
+            .enumerate()
+            .all(|(i, dimension)| i == *dimension)
+        {
+            Layout::ColumnMajor
+        } else {
+            Layout::Order(order.to_vec())
+        }
+    }
+
+    fn with_layout(mut self, layout: Layout) -> Result<Self, DimensionMismatchingError> {
+        self.set_layout(layout)?;
+        Ok(self)
+    }
+
+    fn lower_bounds(&self) -> &[isize];
+    fn set_lower_bound_of_dimension(
+        &mut self,
+        dimension: usize,
+        lower_bound: isize,
+    ) -> Result<(), DimensionMismatchingError>;
+
+    fn lower_bound_of_dimension(
+        &self,
+        dimension: usize,
+    ) -> Result<isize, DimensionMismatchingError> {
+        if dimension >= self.dimension() {
+            Err(DimensionMismatchingError {
+                dimension: self.dimension(),
+                vector_dimension: dimension,
+            })
+        } else {
+            Ok(self.lower_bounds()[dimension])
+        }
+    }
+
+    fn range_of_dimension(
+        &self,
+        dimension: usize,
+    ) -> Result<Range<isize>, DimensionMismatchingError> {
+        let lower_bound = self.lower_bound_of_dimension(dimension)?;
+        Ok(lower_bound..(lower_bound + self.shape()[dimension] as isize))
+    }
+
+    fn names(&self) -> &[Option<Cow<'static, str>>];
+    fn set_name_of_dimension(
+        &mut self,
+        dimension: usize,
+        name: Option<Cow<'static, str>>,
+    ) -> Result<(), DimensionMismatchingError>;
+
+    fn name_of_dimension(&self, dimension: usize) -> Option<&str> {
+        self.names().get(dimension)?.as_deref()
+    }
+
+    fn dimension_of_name(&self, name: &str) -> Option<usize> {
+        (0..self.dimension()).find(|i| self.name_of_dimension(*i) == Some(name))
+    }
+
     fn len_of_dimension(&self, dimension: usize) -> Result<usize, DimensionMismatchingError> {
-        if dimension > Self::DIMENSION {
+        if dimension >= self.dimension() {
             Err(DimensionMismatchingError {
-                dimension: Self::DIMENSION,
+                dimension: self.dimension(),
                 vector_dimension: dimension,
             })
         } else {
@@ -107,9 +370,9 @@ pub trait Shape {
     }
 
     fn offset_of_dimension(&self, dimension: usize) -> Result<usize, DimensionMismatchingError> {
-        if dimension > Self::DIMENSION {
+        if dimension >= self.dimension() {
             Err(DimensionMismatchingError {
-                dimension: Self::DIMENSION,
+                dimension: self.dimension(),
                 vector_dimension: dimension,
             })
         } else {
@@ -117,8 +380,9 @@ pub trait Shape {
         }
     }
 
+    // each index of the vector is resolved by `actual_index`, like the indexes of a dummy vector
     fn index(&self, vector: &Self::VectorType) -> Result<usize, IndexCalculationError> {
-        if Self::dimension_of(vector) > self.dimension() {
+        if Self::dimension_of(vector) != self.dimension() {
             Err(IndexCalculationError::DimensionMismatching(
                 DimensionMismatchingError {
                     dimension: self.dimension(),
@@ -128,14 +392,18 @@ pub trait Shape {
         } else {
             let mut index = 0;
             for i in 0..self.dimension() {
-                if vector[i] > self.len_of_dimension(i).unwrap() {
-                    return Err(IndexCalculationError::OutOfShape(OutOfShapeError {
-                        dimension: i,
-                        len: self.len_of_dimension(i).unwrap(),
-                        vector_index: vector[i] as isize,
-                    }));
+                match self.actual_index(i, vector[i]) {
+                    Some(value) => index += value * self.offset_of_dimension(i).unwrap(),
+                    None => {
+                        return Err(IndexCalculationError::OutOfShape(OutOfShapeError {
+                            dimension: i,
+                            name: self.names()[i].clone(),
+                            lower_bound: self.lower_bound_of_dimension(i).unwrap(),
+                            len: self.len_of_dimension(i).unwrap(),
+                            vector_index: vector[i],
+                        }))
+                    }
                 }
-                index += vector[i] * self.offset_of_dimension(i).unwrap();
             }
             Ok(index)
         }
@@ -143,78 +411,278 @@ pub trait Shape {
 
     fn vector(&self, mut index: usize) -> Self::VectorType {
         let mut vector = self.zero();
-        for i in 0..self.dimension() {
+        for i in self.order().iter().copied() {
             let offset = self.offset_of_dimension(i).unwrap();
-            vector[i] = index / offset;
+            vector[i] = (index / offset) as isize + self.lower_bounds()[i];
             index = index % offset;
         }
         vector
     }
 
     fn next_vector(&self, vector: &mut Self::VectorType) -> bool {
+        let order = self.order();
         let mut carry = false;
-        vector[self.dimension() - 1] += 1;
+        vector[order[self.dimension() - 1]] += 1;
 
-        for i in (0..self.dimension()).rev() {
+        for i in order.iter().rev().copied() {
             if carry {
                 vector[i] += 1;
                 carry = false;
             }
-            if vector[i] == self.len_of_dimension(i).unwrap() {
-                vector[i] = 0;
+            let range = self.range_of_dimension(i).unwrap();
+            if vector[i] == range.end {
+                vector[i] = range.start;
                 carry = true;
             }
         }
         !carry
     }
 
+    fn broadcast(&self, shape: &Self) -> Result<Self, IndexCalculationError> {
+        let dimension = std::cmp::max(self.dimension(), shape.dimension());
+        let len_of = |this: &Self, i: usize| {
+            if i + this.dimension() >= dimension {
+                this.shape()[i + this.dimension() - dimension]
+            } else {
+                1
+            }
+        };
+        // a dimension keeps its lower bound and name only if every operand spanning it agrees
+        let label_of = |this: &Self, i: usize, len: usize| {
+            if i + this.dimension() >= dimension
+                && this.shape()[i + this.dimension() - dimension] == len
+            {
+                let j = i + this.dimension() - dimension;
+                Some((this.lower_bounds()[j], this.names()[j].clone()))
+            } else {
+                None
+            }
+        };
+        let mut ret = Vec::with_capacity(dimension);
+        let mut lower_bounds = Vec::with_capacity(dimension);
+        let mut names = Vec::with_capacity(dimension);
+        for i in 0..dimension {
+            let (lhs, rhs) = (len_of(self, i), len_of(shape, i));
+            let len = if lhs == rhs || rhs == 1 {
+                lhs
+            } else if lhs == 1 {
+                rhs
+            } else {
+                return Err(IndexCalculationError::ShapeMismatching(
+                    ShapeMismatchingError {
+                        dimension: i,
+                        len: lhs,
+                        broadcast_len: rhs,
+                    },
+                ));
+            };
+            ret.push(len);
+            match (label_of(self, i, len), label_of(shape, i, len)) {
+                (Some(lhs), Some(rhs)) => {
+                    lower_bounds.push(if lhs.0 == rhs.0 { lhs.0 } else { 0 });
+                    names.push(if lhs.1 == rhs.1 { lhs.1 } else { None });
+                }
+                (Some(label), None) | (None, Some(label)) => {
+                    lower_bounds.push(label.0);
+                    names.push(label.1);
+                }
+                (None, None) => {
+                    lower_bounds.push(0);
+                    names.push(None);
+                }
+            }
+        }
+        let ret = Self::from_slice(&ret).map_err(IndexCalculationError::DimensionMismatching)?;
+        Ok(with_names(with_lower_bounds(ret, &lower_bounds), &names))
+    }
+
+    // negative indexes count from the end only in dimensions starting from zero,
+    // in a dimension with another lower bound they are plain logical indexes
     fn actual_index(&self, dimension: usize, index: isize) -> Option<usize> {
         let len = self.len_of_dimension(dimension).unwrap();
-        if index >= (len as isize) || index < -(len as isize) {
+        let lower_bound = self.lower_bound_of_dimension(dimension).unwrap();
+        if lower_bound != 0 {
+            if self.range_of_dimension(dimension).unwrap().contains(&index) {
+                Some((index - lower_bound) as usize)
+            } else {
+                None
+            }
+        } else if index >= (len as isize) || index < -(len as isize) {
             None
         } else {
-            Some((index % (len as isize)) as usize)
+            Some(((index + (len as isize)) % (len as isize)) as usize)
+        }
+    }
+}
+
+fn format_shape<S: Shape>(f: &mut fmt::Formatter<'_>, shape: &S) -> fmt::Result {
+    write!(f, "(")?;
+    for (i, len) in shape.shape().iter().enumerate() {
+        if i != 0 {
+            write!(f, ", ")?;
+        }
+        if let Some(name) = shape.name_of_dimension(i) {
+            write!(f, "{}: ", name)?;
         }
+        match shape.lower_bounds()[i] {
+            0 => write!(f, "{}", len)?,
+            lower_bound => write!(f, "{}..{}", lower_bound, lower_bound + *len as isize)?,
+        }
+    }
+    if shape.dimension() == 1 {
+        write!(f, ",")?;
+    }
+    write!(f, ")")
+}
+
+pub(crate) fn with_names<S: Shape>(mut shape: S, names: &[Option<Cow<'static, str>>]) -> S {
+    for (i, name) in names.iter().enumerate() {
+        shape.set_name_of_dimension(i, name.clone()).unwrap();
     }
+    shape
+}
+
+pub(crate) fn with_lower_bounds<S: Shape>(mut shape: S, lower_bounds: &[isize]) -> S {
+    for (i, lower_bound) in lower_bounds.iter().enumerate() {
+        shape.set_lower_bound_of_dimension(i, *lower_bound).unwrap();
+    }
+    shape
+}
+
+pub(crate) fn row_major<S: Shape>(shape: &S) -> S {
+    shape.clone().with_layout(Layout::RowMajor).unwrap()
+}
+
+fn set_lower_bound_of(
+    lower_bounds: &mut [isize],
+    dimension: usize,
+    lower_bound: isize,
+) -> Result<(), DimensionMismatchingError> {
+    if dimension >= lower_bounds.len() {
+        return Err(DimensionMismatchingError {
+            dimension: lower_bounds.len(),
+            vector_dimension: dimension,
+        });
+    }
+    lower_bounds[dimension] = lower_bound;
+    Ok(())
+}
+
+fn order_of(layout: Layout, dimension: usize) -> Result<Vec<usize>, DimensionMismatchingError> {
+    match layout {
+        Layout::RowMajor => Ok((0..dimension).collect()),
+        Layout::ColumnMajor => Ok((0..dimension).rev().collect()),
+        Layout::Order(order) => {
+            if order.len() != dimension {
+                return Err(DimensionMismatchingError {
+                    dimension,
+                    vector_dimension: order.len(),
+                });
+            }
+            for (i, axis) in order.iter().enumerate() {
+                if *axis >= dimension || order[..i].contains(axis) {
+                    return Err(DimensionMismatchingError {
+                        dimension,
+                        vector_dimension: *axis,
+                    });
+                }
+            }
+            Ok(order)
+        }
+    }
+}
+
+fn set_name_of(
+    names: &mut [Option<Cow<'static, str>>],
+    dimension: usize,
+    name: Option<Cow<'static, str>>,
+) -> Result<(), DimensionMismatchingError> {
+    if dimension >= names.len() {
+        return Err(DimensionMismatchingError {
+            dimension: names.len(),
+            vector_dimension: dimension,
+        });
+    }
+    names[dimension] = name;
+    Ok(())
 }
 
 pub(self) fn offset<const DIMENSION: usize>(
     shape: &[usize; DIMENSION],
+    order: &[usize; DIMENSION],
 ) -> ([usize; DIMENSION], usize) {
     let mut offset: [usize; DIMENSION] = unsafe { mem::zeroed() };
 
-    offset[shape.len() - 1] = 1;
     let mut len = 1;
-    for i in (0..(shape.len() - 1)).rev() {
-        offset[i] = offset[i + 1] * shape[i + 1];
-        len *= shape[i + 1];
+    for i in order.iter().rev() {
+        offset[*i] = len;
+        len *= shape[*i];
     }
-    len *= shape[0];
     (offset, len)
 }
 
-#[derive(Clone, Copy)]
-pub struct Shape1 {
-    pub(self) shape: [usize; 1],
+#[derive(Clone)]
+pub struct StaticShape<const N: usize> {
+    pub(self) shape: [usize; N],
+    pub(self) offset: [usize; N],
+    pub(self) order: [usize; N],
+    pub(self) len: usize,
+    pub(self) lower_bounds: [isize; N],
+    pub(self) names: [Option<Cow<'static, str>>; N],
 }
 
-impl Shape1 {
-    pub fn new(shape: [usize; 1]) -> Self {
-        Self { shape: shape }
+impl<const N: usize> StaticShape<N> {
+    pub(self) const NOT_EMPTY: () = assert!(N > 0, "a static shape needs at least one dimension");
+
+    pub fn new(shape: [usize; N]) -> Self {
+        let () = Self::NOT_EMPTY;
+        let order = std::array::from_fn(|i| i);
+        let (offset, len) = offset(&shape, &order);
+        Self {
+            shape,
+            offset,
+            order,
+            len,
+            lower_bounds: [0; N],
+            names: std::array::from_fn(|_| None),
+        }
+    }
+
+    pub fn with_names<Name: Into<Cow<'static, str>>>(mut self, names: [Name; N]) -> Self {
+        self.names = names.map(|name| Some(name.into()));
+        self
+    }
+
+    pub fn with_lower_bounds(mut self, lower_bounds: [isize; N]) -> Self {
+        self.lower_bounds = lower_bounds;
+        self
     }
 }
 
-impl Shape for Shape1 {
-    const DIMENSION: usize = 1;
-    type VectorType = [usize; 1];
-    type DummyVectorType = [DummyIndex; 1];
+impl<const N: usize> Shape for StaticShape<N> {
+    const DIMENSION: usize = N;
+    type VectorType = [isize; N];
+    type DummyVectorType = [DummyIndex; N];
+    type MapVectorType<'a> = [MapIndex<'a>; N];
+
+    fn from_slice(shape: &[usize]) -> Result<Self, DimensionMismatchingError> {
+        if shape.len() != N {
+            return Err(DimensionMismatchingError {
+                dimension: N,
+                vector_dimension: shape.len(),
+            });
+        }
+        let mut value = [0; N];
+        value.copy_from_slice(shape);
+        Ok(Self::new(value))
+    }
 
     fn zero(&self) -> Self::VectorType {
-        [0]
+        self.lower_bounds
     }
 
     fn len(&self) -> usize {
-        self.shape[0]
+        self.len
     }
 
     fn shape(&self) -> &[usize] {
@@ -222,110 +690,234 @@ impl Shape for Shape1 {
     }
 
     fn offset(&self) -> &[usize] {
-        &self.shape
+        &self.offset
+    }
+
+    fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    fn set_layout(&mut self, layout: Layout) -> Result<(), DimensionMismatchingError> {
+        self.order.copy_from_slice(&order_of(layout, N)?);
+        self.offset = offset(&self.shape, &self.order).0;
+        Ok(())
+    }
+
+    fn lower_bounds(&self) -> &[isize] {
+        &self.lower_bounds
+    }
+
+    fn set_lower_bound_of_dimension(
+        &mut self,
+        dimension: usize,
+        lower_bound: isize,
+    ) -> Result<(), DimensionMismatchingError> {
+        set_lower_bound_of(&mut self.lower_bounds, dimension, lower_bound)
+    }
+
+    fn names(&self) -> &[Option<Cow<'static, str>>] {
+        &self.names
+    }
+
+    fn set_name_of_dimension(
+        &mut self,
+        dimension: usize,
+        name: Option<Cow<'static, str>>,
+    ) -> Result<(), DimensionMismatchingError> {
+        set_name_of(&mut self.names, dimension, name)
     }
 }
 
-macro_rules! shape {
-    ($type:ident, $dim:expr) => {
-        #[derive(Clone, Copy)]
-        pub struct $type {
-            pub(self) shape: [usize; $dim],
-            pub(self) offset: [usize; $dim],
-            pub(self) len: usize,
-        }
+impl<const N: usize> fmt::Display for StaticShape<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format_shape(f, self)
+    }
+}
 
-        impl $type {
-            pub fn new(shape: [usize; $dim]) -> Self {
-                let (offset, len) = offset(&shape);
-                Self {
-                    shape: shape,
-                    offset: offset,
-                    len: len,
-                }
-            }
-        }
+impl<const N: usize> fmt::Debug for StaticShape<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Shape{}{}", N, self)
+    }
+}
 
-        impl Shape for $type {
-            const DIMENSION: usize = $dim;
-            type VectorType = [usize; $dim];
-            type DummyVectorType = [DummyIndex; $dim];
+pub type Shape1 = StaticShape<1>;
+pub type Shape2 = StaticShape<2>;
+pub type Shape3 = StaticShape<3>;
+pub type Shape4 = StaticShape<4>;
+pub type Shape5 = StaticShape<5>;
+pub type Shape6 = StaticShape<6>;
+pub type Shape7 = StaticShape<7>;
+pub type Shape8 = StaticShape<8>;
+pub type Shape9 = StaticShape<9>;
+pub type Shape10 = StaticShape<10>;
+pub type Shape11 = StaticShape<11>;
+pub type Shape12 = StaticShape<12>;
+pub type Shape13 = StaticShape<13>;
+pub type Shape14 = StaticShape<14>;
+pub type Shape15 = StaticShape<15>;
+pub type Shape16 = StaticShape<16>;
+pub type Shape17 = StaticShape<17>;
+pub type Shape18 = StaticShape<18>;
+pub type Shape19 = StaticShape<19>;
+pub type Shape20 = StaticShape<20>;
 
-            fn zero(&self) -> Self::VectorType {
-                unsafe { mem::zeroed() }
-            }
+/// Implemented for every `StaticShape<N>` and `DynShape`; reducing a `Shape1` fails to compile.
+/// Calling it on a static shape needs `#![feature(generic_const_exprs)]` in the calling crate too.
+pub trait ReducibleShape: Shape {
+    type ReducedShape: Shape;
 
-            fn len(&self) -> usize {
-                self.len
-            }
+    fn reduce(&self, dimension: usize) -> Result<Self::ReducedShape, DimensionMismatchingError> {
+        if dimension >= self.dimension() {
+            return Err(DimensionMismatchingError {
+                dimension: self.dimension(),
+                vector_dimension: dimension,
+            });
+        }
+        let shape: Vec<usize> = (0..self.dimension())
+            .filter(|i| *i != dimension)
+            .map(|i| self.shape()[i])
+            .collect();
+        let names: Vec<_> = (0..self.dimension())
+            .filter(|i| *i != dimension)
+            .map(|i| self.names()[i].clone())
+            .collect();
+        let lower_bounds: Vec<_> = (0..self.dimension())
+            .filter(|i| *i != dimension)
+            .map(|i| self.lower_bounds()[i])
+            .collect();
+        Ok(with_lower_bounds(
+            with_names(Self::ReducedShape::from_slice(&shape)?, &names),
+            &lower_bounds,
+        ))
+    }
+}
 
-            fn shape(&self) -> &[usize] {
-                &self.shape
-            }
+impl<const N: usize> ReducibleShape for StaticShape<N>
+where
+    [(); N - 1]:,
+{
+    type ReducedShape = StaticShape<{ N - 1 }>;
+}
 
-            fn offset(&self) -> &[usize] {
-                &self.offset
-            }
+/// Implemented for every `StaticShape<N>` and `DynShape`, with the same feature requirement as
+/// `ReducibleShape`.
+pub trait ExpandableShape: Shape {
+    type ExpandedShape: Shape;
+
+    fn expand(
+        &self,
+        dimension: usize,
+        len: usize,
+    ) -> Result<Self::ExpandedShape, DimensionMismatchingError> {
+        if dimension > self.dimension() {
+            return Err(DimensionMismatchingError {
+                dimension: self.dimension(),
+                vector_dimension: dimension,
+            });
         }
-    };
-}
-
-shape!(Shape2, 2);
-shape!(Shape3, 3);
-shape!(Shape4, 4);
-shape!(Shape5, 5);
-shape!(Shape6, 6);
-shape!(Shape7, 7);
-shape!(Shape8, 8);
-shape!(Shape9, 9);
-shape!(Shape10, 10);
-shape!(Shape11, 11);
-shape!(Shape12, 12);
-shape!(Shape13, 13);
-shape!(Shape14, 14);
-shape!(Shape15, 15);
-shape!(Shape16, 16);
-shape!(Shape17, 17);
-shape!(Shape18, 18);
-shape!(Shape19, 19);
-shape!(Shape20, 20);
+        let mut shape = self.shape().to_vec();
+        shape.insert(dimension, len);
+        let mut names = self.names().to_vec();
+        names.insert(dimension, None);
+        let mut lower_bounds = self.lower_bounds().to_vec();
+        lower_bounds.insert(dimension, 0);
+        Ok(with_lower_bounds(
+            with_names(Self::ExpandedShape::from_slice(&shape)?, &names),
+            &lower_bounds,
+        ))
+    }
+}
+
+impl<const N: usize> ExpandableShape for StaticShape<N>
+where
+    [(); N + 1]:,
+{
+    type ExpandedShape = StaticShape<{ N + 1 }>;
+}
 
+#[derive(Clone)]
 pub struct DynShape {
     pub(self) shape: Vec<usize>,
     pub(self) offset: Vec<usize>,
+    pub(self) order: Vec<usize>,
     pub(self) len: usize,
+    pub(self) lower_bounds: Vec<isize>,
+    pub(self) names: Vec<Option<Cow<'static, str>>>,
 }
 
 impl DynShape {
     pub fn new(shape: Vec<usize>) -> Self {
-        let (offset, len) = Self::offset(&shape);
+        let order: Vec<usize> = (0..shape.len()).collect();
+        let (offset, len) = Self::offset(&shape, &order);
+        let lower_bounds = vec![0; shape.len()];
+        let names = vec![None; shape.len()];
         Self {
             shape: shape,
             offset: offset,
+            order,
             len: len,
+            lower_bounds,
+            names,
         }
     }
 
-    pub(self) fn offset(shape: &Vec<usize>) -> (Vec<usize>, usize) {
+    pub fn with_names<Name: Into<Cow<'static, str>> + Clone>(
+        mut self,
+        names: &[Name],
+    ) -> Result<Self, DimensionMismatchingError> {
+        if names.len() != self.shape.len() {
+            return Err(DimensionMismatchingError {
+                dimension: self.shape.len(),
+                vector_dimension: names.len(),
+            });
+        }
+        self.names = names.iter().map(|name| Some(name.clone().into())).collect();
+        Ok(self)
+    }
+
+    pub fn with_lower_bounds(
+        mut self,
+        lower_bounds: &[isize],
+    ) -> Result<Self, DimensionMismatchingError> {
+        if lower_bounds.len() != self.shape.len() {
+            return Err(DimensionMismatchingError {
+                dimension: self.shape.len(),
+                vector_dimension: lower_bounds.len(),
+            });
+        }
+        self.lower_bounds = lower_bounds.to_vec();
+        Ok(self)
+    }
+
+    pub(self) fn offset(shape: &[usize], order: &[usize]) -> (Vec<usize>, usize) {
         let mut offset: Vec<usize> = (0..shape.len()).map(|_| 0).collect();
-        offset[shape.len() - 1] = 1;
         let mut len = 1;
-        for i in (0..(shape.len() - 1)).rev() {
-            offset[i] = offset[i + 1] * shape[i + 1];
-            len *= shape[i + 1];
+        for i in order.iter().rev() {
+            offset[*i] = len;
+            len *= shape[*i];
         }
-        len *= shape[0];
         (offset, len)
     }
 }
 
 impl Shape for DynShape {
     const DIMENSION: usize = DYN_DIMENSION;
-    type VectorType = Vec<usize>;
+    type VectorType = Vec<isize>;
     type DummyVectorType = Vec<DummyIndex>;
+    type MapVectorType<'a> = Vec<MapIndex<'a>>;
+
+    fn from_slice(shape: &[usize]) -> Result<Self, DimensionMismatchingError> {
+        if shape.is_empty() {
+            return Err(DimensionMismatchingError {
+                dimension: 1,
+                vector_dimension: 0,
+            });
+        }
+        Ok(Self::new(shape.to_vec()))
+    }
 
     fn zero(&self) -> Self::VectorType {
-        (0..self.shape.len()).map(|_| 0).collect()
+        self.lower_bounds.clone()
     }
 
     fn len(&self) -> usize {
@@ -347,4 +939,161 @@ impl Shape for DynShape {
     fn offset(&self) -> &[usize] {
         &self.offset
     }
+
+    fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    fn set_layout(&mut self, layout: Layout) -> Result<(), DimensionMismatchingError> {
+        self.order = order_of(layout, self.shape.len())?;
+        self.offset = Self::offset(&self.shape, &self.order).0;
+        Ok(())
+    }
+
+    fn lower_bounds(&self) -> &[isize] {
+        &self.lower_bounds
+    }
+
+    fn set_lower_bound_of_dimension(
+        &mut self,
+        dimension: usize,
+        lower_bound: isize,
+    ) -> Result<(), DimensionMismatchingError> {
+        set_lower_bound_of(&mut self.lower_bounds, dimension, lower_bound)
+    }
+
+    fn names(&self) -> &[Option<Cow<'static, str>>] {
+        &self.names
+    }
+
+    fn set_name_of_dimension(
+        &mut self,
+        dimension: usize,
+        name: Option<Cow<'static, str>>,
+    ) -> Result<(), DimensionMismatchingError> {
+        set_name_of(&mut self.names, dimension, name)
+    }
+}
+
+impl fmt::Display for DynShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format_shape(f, self)
+    }
+}
+
+impl fmt::Debug for DynShape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DynShape{}", self)
+    }
+}
+
+impl ReducibleShape for DynShape {
+    type ReducedShape = DynShape;
+}
+
+impl ExpandableShape for DynShape {
+    type ExpandedShape = DynShape;
+}
+
+pub trait Axis {
+    fn dimension_of<S: Shape>(&self, shape: &S) -> Result<usize, UnknownDimensionError>;
+}
+
+impl Axis for usize {
+    fn dimension_of<S: Shape>(&self, _: &S) -> Result<usize, UnknownDimensionError> {
+        Ok(*self)
+    }
+}
+
+impl Axis for &str {
+    fn dimension_of<S: Shape>(&self, shape: &S) -> Result<usize, UnknownDimensionError> {
+        shape
+            .dimension_of_name(self)
+            .ok_or_else(|| UnknownDimensionError {
+                name: Cow::Owned(self.to_string()),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn reduce_first<const N: usize>(shape: &StaticShape<N>) -> StaticShape<{ N - 1 }>
+    where
+        [(); N - 1]:,
+    {
+        shape.reduce(0).unwrap()
+    }
+
+    #[test]
+    fn static_shape() {
+        let shape = StaticShape::<4>::new([2, 3, 4, 5]).with_lower_bounds([0, 1, 0, 0]);
+        assert_eq!(shape.len(), 120);
+        assert_eq!(shape.offset(), &[60, 20, 5, 1]);
+        assert_eq!(shape.index(&[1, 1, 0, 0]).unwrap(), 60);
+        assert_eq!(shape.vector(61), [1, 1, 0, 1]);
+        let reduced = reduce_first(&shape);
+        assert_eq!(reduced.shape(), &[3, 4, 5]);
+        assert_eq!(reduced.lower_bounds(), &[1, 0, 0]);
+        assert_eq!(format!("{:?}", reduced), "Shape3(1..4, 4, 5)");
+        let expanded = reduced.expand(3, 2).unwrap();
+        assert_eq!(expanded.shape(), &[3, 4, 5, 2]);
+    }
+
+    #[test]
+    fn more_than_twenty_dimensions() {
+        let mut lens = [1; 21];
+        lens[20] = 3;
+        lens[0] = 2;
+        let array =
+            MultiArray::<i32, StaticShape<21>>::new_by(StaticShape::new(lens), |i| i as i32);
+        let sums = array.sum_axis(0).unwrap();
+        assert_eq!(sums.shape().dimension(), 20);
+        assert_eq!(sums.iter().cloned().collect::<Vec<_>>(), vec![3, 5, 7]);
+        let stacked = MultiArray::stack(&[&array, &array]).unwrap();
+        assert_eq!(stacked.shape().dimension(), 22);
+        assert_eq!(stacked.len(), 12);
+        let concatenated = MultiArray::concatenate(20, &[&array, &array]).unwrap();
+        assert_eq!(concatenated.shape().shape()[20], 6);
+    }
+
+    #[test]
+    fn runtime_names() {
+        let names: Vec<String> = (0..2).map(|i| format!("axis{}", i)).collect();
+        let shape = DynShape::new(vec![2, 3]).with_names(&names).unwrap();
+        assert_eq!(format!("{}", shape), "(axis0: 2, axis1: 3)");
+        assert_eq!(names[1].as_str().dimension_of(&shape).unwrap(), 1);
+        let err = "axis2".dimension_of(&shape).err().unwrap();
+        assert_eq!(err.to_string(), "There is no dimension named axis2.");
+        let array = DynMultiArray::<i32>::new(shape);
+        let err = array.get(dyn_dummy!(0, 3)).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Length of dimension 1 (axis1) is 3, but it get 3."
+        );
+    }
+
+    #[test]
+    fn broadcast_keeps_labels() {
+        let lhs = Shape2::new([2, 3])
+            .with_lower_bounds([1, -1])
+            .with_names(["row", "column"]);
+        let rhs = Shape2::new([1, 3]).with_lower_bounds([0, -1]);
+        let shape = lhs.broadcast(&rhs).unwrap();
+        assert_eq!(shape.lower_bounds(), &[1, -1]);
+        assert_eq!(shape.name_of_dimension(0), Some("row"));
+        assert_eq!(shape.name_of_dimension(1), None);
+
+        let array =
+            MultiArray1::<i32>::new_by(Shape1::new([3]).with_lower_bounds([1]), |i| i as i32);
+        let sum = (&array + &array).unwrap();
+        assert_eq!(sum.shape().range_of_dimension(0).unwrap(), 1..4);
+        assert_eq!(sum[&[1]], 0);
+        assert_eq!(sum[&[3]], 4);
+
+        let other = MultiArray1::<i32>::new_by(Shape1::new([3]), |i| i as i32);
+        let sum = (&array + &other).unwrap();
+        assert_eq!(sum.shape().lower_bounds(), &[0]);
+    }
 }
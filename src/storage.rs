@@ -0,0 +1,240 @@
+use std::collections::{btree_map, BTreeMap};
+use std::iter::{Enumerate, FilterMap, Map};
+use std::mem;
+use std::slice;
+use std::vec;
+
+pub trait Storage<T: Sized> {
+    type Iter<'a>: Iterator<Item = (usize, &'a T)>
+    where
+        Self: 'a,
+        T: 'a;
+    type IterMut<'a>: Iterator<Item = (usize, &'a mut T)>
+    where
+        Self: 'a,
+        T: 'a;
+    type IntoIter: Iterator<Item = (usize, T)>;
+
+    fn len_of_cells(&self) -> usize;
+
+    fn cell(&self, index: usize) -> Option<&T>;
+    fn cell_mut(&mut self, index: usize) -> Option<&mut T>;
+    fn set_cell(&mut self, index: usize, value: T) -> Option<T>;
+    fn swap_cells(&mut self, lhs: usize, rhs: usize);
+
+    fn cells(&self) -> Self::Iter<'_>;
+    fn cells_mut(&mut self) -> Self::IterMut<'_>;
+    fn into_cells(self) -> Self::IntoIter;
+}
+
+pub trait PartialStorage<T: Sized>: Storage<T> {
+    fn empty(len: usize) -> Self;
+
+    fn take_cell(&mut self, index: usize) -> Option<T>;
+}
+
+impl<T: Sized> Storage<T> for Vec<Option<T>> {
+    type Iter<'a>
+        = FilterMap<
+        Enumerate<slice::Iter<'a, Option<T>>>,
+        fn((usize, &'a Option<T>)) -> Option<(usize, &'a T)>,
+    >
+    where
+        T: 'a;
+    type IterMut<'a>
+        = FilterMap<
+        Enumerate<slice::IterMut<'a, Option<T>>>,
+        fn((usize, &'a mut Option<T>)) -> Option<(usize, &'a mut T)>,
+    >
+    where
+        T: 'a;
+    type IntoIter = FilterMap<
+        Enumerate<vec::IntoIter<Option<T>>>,
+        fn((usize, Option<T>)) -> Option<(usize, T)>,
+    >;
+
+    fn len_of_cells(&self) -> usize {
+        self.len()
+    }
+
+    fn cell(&self, index: usize) -> Option<&T> {
+        self[index].as_ref()
+    }
+
+    fn cell_mut(&mut self, index: usize) -> Option<&mut T> {
+        self[index].as_mut()
+    }
+
+    fn set_cell(&mut self, index: usize, value: T) -> Option<T> {
+        self[index].replace(value)
+    }
+
+    fn swap_cells(&mut self, lhs: usize, rhs: usize) {
+        self.swap(lhs, rhs)
+    }
+
+    fn cells(&self) -> Self::Iter<'_> {
+        self.iter()
+            .enumerate()
+            .filter_map(|(index, cell)| cell.as_ref().map(|value| (index, value)))
+    }
+
+    fn cells_mut(&mut self) -> Self::IterMut<'_> {
+        self.iter_mut()
+            .enumerate()
+            .filter_map(|(index, cell)| cell.as_mut().map(|value| (index, value)))
+    }
+
+    fn into_cells(self) -> Self::IntoIter {
+        self.into_iter()
+            .enumerate()
+            .filter_map(|(index, cell)| cell.map(|value| (index, value)))
+    }
+}
+
+impl<T: Sized> PartialStorage<T> for Vec<Option<T>> {
+    fn empty(len: usize) -> Self {
+        (0..len).map(|_| Option::None).collect()
+    }
+
+    fn take_cell(&mut self, index: usize) -> Option<T> {
+        self[index].take()
+    }
+}
+
+impl<T: Sized> Storage<T> for Vec<T> {
+    type Iter<'a>
+        = Enumerate<slice::Iter<'a, T>>
+    where
+        T: 'a;
+    type IterMut<'a>
+        = Enumerate<slice::IterMut<'a, T>>
+    where
+        T: 'a;
+    type IntoIter = Enumerate<vec::IntoIter<T>>;
+
+    fn len_of_cells(&self) -> usize {
+        self.len()
+    }
+
+    fn cell(&self, index: usize) -> Option<&T> {
+        Some(&self[index])
+    }
+
+    fn cell_mut(&mut self, index: usize) -> Option<&mut T> {
+        Some(&mut self[index])
+    }
+
+    fn set_cell(&mut self, index: usize, value: T) -> Option<T> {
+        Some(mem::replace(&mut self[index], value))
+    }
+
+    fn swap_cells(&mut self, lhs: usize, rhs: usize) {
+        self.swap(lhs, rhs)
+    }
+
+    fn cells(&self) -> Self::Iter<'_> {
+        self.iter().enumerate()
+    }
+
+    fn cells_mut(&mut self) -> Self::IterMut<'_> {
+        self.iter_mut().enumerate()
+    }
+
+    fn into_cells(self) -> Self::IntoIter {
+        self.into_iter().enumerate()
+    }
+}
+
+pub struct SparseStorage<T: Sized> {
+    pub(self) map: BTreeMap<usize, T>,
+    pub(self) len: usize,
+}
+
+impl<T: Sized> SparseStorage<T> {
+    pub fn new(len: usize) -> Self {
+        Self {
+            map: BTreeMap::new(),
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn count(&self) -> usize {
+        self.map.len()
+    }
+}
+
+impl<T: Sized> Storage<T> for SparseStorage<T> {
+    type Iter<'a>
+        = Map<btree_map::Iter<'a, usize, T>, fn((&'a usize, &'a T)) -> (usize, &'a T)>
+    where
+        T: 'a;
+    type IterMut<'a>
+        = Map<btree_map::IterMut<'a, usize, T>, fn((&'a usize, &'a mut T)) -> (usize, &'a mut T)>
+    where
+        T: 'a;
+    type IntoIter = btree_map::IntoIter<usize, T>;
+
+    fn len_of_cells(&self) -> usize {
+        self.len
+    }
+
+    fn cell(&self, index: usize) -> Option<&T> {
+        self.map.get(&index)
+    }
+
+    fn cell_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.map.get_mut(&index)
+    }
+
+    fn set_cell(&mut self, index: usize, value: T) -> Option<T> {
+        if index >= self.len {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            );
+        }
+        self.map.insert(index, value)
+    }
+
+    fn swap_cells(&mut self, lhs: usize, rhs: usize) {
+        let lhs_value = self.map.remove(&lhs);
+        let rhs_value = self.map.remove(&rhs);
+        if let Some(value) = lhs_value {
+            self.set_cell(rhs, value);
+        }
+        if let Some(value) = rhs_value {
+            self.set_cell(lhs, value);
+        }
+    }
+
+    fn cells(&self) -> Self::Iter<'_> {
+        self.map.iter().map(|(index, value)| (*index, value))
+    }
+
+    fn cells_mut(&mut self) -> Self::IterMut<'_> {
+        self.map.iter_mut().map(|(index, value)| (*index, value))
+    }
+
+    fn into_cells(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
+impl<T: Sized> PartialStorage<T> for SparseStorage<T> {
+    fn empty(len: usize) -> Self {
+        Self::new(len)
+    }
+
+    fn take_cell(&mut self, index: usize) -> Option<T> {
+        self.map.remove(&index)
+    }
+}